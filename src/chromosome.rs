@@ -0,0 +1,247 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::Encoding;
+use crate::genealogy::Origin;
+use crate::objective::Objective;
+
+/// Domyślna liczba bitów kodujących jedną zmienną.
+/// 16 bitów daje rozdzielczość ~0.0003 na dziedzinie [-10, 10].
+pub const BITS: usize = 16;
+
+/// Dopuszczalny zakres liczby bitów jednej zmiennej.
+pub const MIN_BITS: usize = 4;
+pub const MAX_BITS: usize = 64;
+
+/// Dziedzina i precyzja jednej zmiennej genomu.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VarSpec {
+    pub min:  f64,
+    pub max:  f64,
+    /// Liczba bitów kodujących tę zmienną.
+    pub bits: usize,
+}
+
+impl Default for VarSpec {
+    fn default() -> Self {
+        Self { min: -10.0, max: 10.0, bits: BITS }
+    }
+}
+
+impl VarSpec {
+    /// Największa liczba całkowita zapisywalna na `bits` bitach (2^bits − 1).
+    fn max_int(&self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+
+    /// Odległość między sąsiednimi wartościami, które da się zakodować.
+    pub fn resolution(&self) -> f64 {
+        (self.max - self.min) / self.max_int() as f64
+    }
+
+    /// Najmniejsza liczba bitów dająca co najmniej `decimals` cyfr po
+    /// przecinku na tej dziedzinie: 2^bits − 1 ≥ (max − min) · 10^decimals.
+    pub fn bits_for_precision(&self, decimals: u32) -> usize {
+        let steps = (self.max - self.min) * 10f64.powi(decimals as i32);
+        let bits = (steps + 1.0).log2().ceil();
+        if bits.is_finite() {
+            (bits as usize).clamp(MIN_BITS, MAX_BITS)
+        } else {
+            MAX_BITS
+        }
+    }
+}
+
+/// Sposób przechowywania genów w chromosomie.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Representation {
+    /// Ciąg bitów dekodowany na zmienne (patrz [`crate::encoding`]).
+    #[default]
+    Binary,
+    /// Genami są bezpośrednio wartości zmiennych (patrz [`crate::real`]).
+    Real,
+}
+
+impl Representation {
+    pub const ALL: [Representation; 2] = [Representation::Binary, Representation::Real];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Representation::Binary => "Binarna",
+            Representation::Real   => "Rzeczywista",
+        }
+    }
+}
+
+/// Łączna długość genomu (suma bitów wszystkich zmiennych).
+pub fn genome_len(vars: &[VarSpec]) -> usize {
+    vars.iter().map(|v| v.bits).sum()
+}
+
+/// Pojedynczy chromosom: ciąg bitów reprezentujący punkt (x1, …, xN) dziedziny.
+///
+/// Genom to sklejone odcinki bitów kolejnych zmiennych. Każdy odcinek
+/// interpretowany jest jako liczba całkowita bez znaku [0, 2^bits) – w kodzie
+/// dwójkowym albo w kodzie Graya (patrz [`crate::encoding`]) – a następnie
+/// liniowo mapowany na przedział [min, max] swojej zmiennej.
+///
+/// W reprezentacji rzeczywistej `genes` jest puste, a genami są same `xs`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Chromosome {
+    /// Geny – sklejone bity wszystkich zmiennych (puste w reprezentacji rzeczywistej).
+    /// W pliku zapisywane jako napis z zer i jedynek.
+    #[serde(with = "bit_string")]
+    pub genes: Vec<bool>,
+    /// Wartość funkcji celu f(x) obliczona dla tego chromosomu.
+    pub value: f64,
+    /// Fitness używany przez selekcję – `value` po uwzględnieniu kierunku
+    /// optymalizacji i przekształcenia (patrz [`crate::fitness`]).
+    pub fitness: f64,
+    /// Wartości zmiennych zdekodowane z genów.
+    pub xs: Vec<f64>,
+    /// Numer osobnika, rodzice i operatory, które go utworzyły.
+    pub origin: Origin,
+}
+
+impl Chromosome {
+    /// Tworzy chromosom z podanych genów, dekodując zmienne według `vars`.
+    /// Wartość i fitness pozostają niepoliczone (0.0) – patrz [`Chromosome::evaluate`].
+    pub fn from_genes(genes: Vec<bool>, vars: &[VarSpec], encoding: Encoding) -> Self {
+        let xs = Self::decode(&genes, vars, encoding);
+        Self { genes, value: 0.0, fitness: 0.0, xs, origin: Origin::default() }
+    }
+
+    /// Tworzy chromosom z losowych bitów. Losowe bity są jednostajne w obu
+    /// kodowaniach, więc `encoding` wpływa tylko na dekodowanie.
+    pub fn random<R: Rng>(vars: &[VarSpec], encoding: Encoding, rng: &mut R) -> Self {
+        let genes = (0..genome_len(vars)).map(|_| rng.gen_bool(0.5)).collect();
+        Self::from_genes(genes, vars, encoding)
+    }
+
+    /// Tworzy chromosom reprezentacji rzeczywistej; wartości spoza dziedziny
+    /// są przycinane do [min, max].
+    pub fn from_xs(mut xs: Vec<f64>, vars: &[VarSpec]) -> Self {
+        for (x, v) in xs.iter_mut().zip(vars) {
+            *x = x.clamp(v.min, v.max);
+        }
+        Self { genes: Vec::new(), value: 0.0, fitness: 0.0, xs, origin: Origin::default() }
+    }
+
+    /// Tworzy chromosom reprezentacji rzeczywistej z punktu wylosowanego
+    /// jednostajnie w dziedzinie.
+    pub fn random_real<R: Rng>(vars: &[VarSpec], rng: &mut R) -> Self {
+        let xs = vars.iter().map(|v| rng.gen_range(v.min..=v.max)).collect();
+        Self::from_xs(xs, vars)
+    }
+
+    /// Dekoduje genom na wartości zmiennych w ich dziedzinach.
+    pub fn decode(genes: &[bool], vars: &[VarSpec], encoding: Encoding) -> Vec<f64> {
+        let mut offset = 0;
+        vars.iter()
+            .map(|v| {
+                let segment = &genes[offset..offset + v.bits];
+                offset += v.bits;
+                let int_val = encoding.decode_segment(segment);
+                v.min + (int_val as f64 / v.max_int() as f64) * (v.max - v.min)
+            })
+            .collect()
+    }
+
+    /// Oblicza i zapisuje wartość funkcji celu. Fitness przypisuje potem
+    /// populacja (może zależeć od wszystkich osobników naraz).
+    pub fn evaluate(&mut self, func: &Objective) {
+        self.value = func.eval(&self.xs);
+    }
+
+    /// Zwraca czytelny podgląd: bity (jeśli są) + x + wartość funkcji + fitness selekcji.
+    pub fn display_str(&self) -> String {
+        let summary = format!("x={}  f={:8.4}  fit={:8.4}", self.xs_str(), self.value, self.fitness);
+        if self.genes.is_empty() {
+            return summary;
+        }
+        let bits: String = self.genes.iter().map(|&b| if b { '1' } else { '0' }).collect();
+        format!("{bits}  {summary}")
+    }
+
+    /// Zdekodowany punkt: `x` dla jednej zmiennej, `(x1, …, xN)` dla wielu.
+    pub fn xs_str(&self) -> String {
+        match self.xs.as_slice() {
+            [x] => format!("{x:7.4}"),
+            xs  => {
+                let parts: Vec<String> = xs.iter().map(|x| format!("{x:.4}")).collect();
+                format!("({})", parts.join(", "))
+            }
+        }
+    }
+}
+
+// Geny jako napis "0110…" – czytelniejszy i krótszy niż tablica wartości logicznych.
+mod bit_string {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(genes: &[bool], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(&genes.iter().map(|&b| if b { '1' } else { '0' }).collect::<String>())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<bool>, D::Error> {
+        String::deserialize(d)?
+            .chars()
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _   => Err(de::Error::custom(format!("niedozwolony znak genu: {c:?}"))),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_maps_extreme_segments_to_domain_edges() {
+        let vars = [VarSpec { min: -2.0, max: 6.0, bits: 4 }, VarSpec { min: 0.0, max: 1.0, bits: 8 }];
+        let mut genes = vec![false; 4];
+        genes.extend([true; 8]);
+        assert_eq!(Chromosome::decode(&genes, &vars, Encoding::Binary), vec![-2.0, 1.0]);
+    }
+
+    #[test]
+    fn random_genome_has_length_of_all_segments() {
+        let vars = [VarSpec { bits: 5, ..VarSpec::default() }, VarSpec { bits: 11, ..VarSpec::default() }];
+        let c = Chromosome::random(&vars, Encoding::Binary, &mut crate::rng_from_seed(1));
+        assert_eq!(c.genes.len(), 16);
+        assert_eq!(c.xs.len(), 2);
+    }
+
+    #[test]
+    fn from_xs_clamps_to_domain() {
+        let vars = [VarSpec::default(), VarSpec::default()];
+        let c = Chromosome::from_xs(vec![-50.0, 3.0], &vars);
+        assert_eq!(c.xs, vec![-10.0, 3.0]);
+        assert!(c.genes.is_empty());
+    }
+
+    #[test]
+    fn bits_for_precision_is_the_smallest_sufficient_width() {
+        let v = VarSpec::default();
+        for decimals in 0..=6 {
+            let bits = v.bits_for_precision(decimals);
+            let step = 10f64.powi(-(decimals as i32));
+            assert!(VarSpec { bits, ..v.clone() }.resolution() <= step, "decimals = {decimals}");
+            if bits > MIN_BITS {
+                assert!(VarSpec { bits: bits - 1, ..v.clone() }.resolution() > step, "decimals = {decimals}");
+            }
+        }
+        assert_eq!(v.bits_for_precision(3), 15);
+        assert_eq!(VarSpec { min: 0.0, max: 0.001, bits: 8 }.bits_for_precision(0), MIN_BITS);
+        assert_eq!(v.bits_for_precision(30), MAX_BITS);
+    }
+
+    #[test]
+    fn resolution_spans_domain_in_max_int_steps() {
+        assert_eq!(VarSpec { min: 0.0, max: 15.0, bits: 4 }.resolution(), 1.0);
+        assert_eq!(VarSpec { min: -1.0, max: 1.0, bits: MAX_BITS }.resolution(), 2.0 / u64::MAX as f64);
+    }
+}
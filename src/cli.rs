@@ -0,0 +1,428 @@
+// ---------------------------------------------------------------------------
+// Tryb wsadowy (bez okna): `genetictool2 run [opcje]`
+//
+// Uruchamia tę samą ewolucję co przycisk "Następna generacja" i wypisuje
+// na stdout statystyki funkcji celu każdego pokolenia. Końcową populację
+// i statystyki można też wyeksportować do plików CSV/JSON, a wykres funkcji
+// z końcową populacją zapisać jako SVG albo PNG.
+// ---------------------------------------------------------------------------
+
+use std::path::PathBuf;
+
+use eframe::egui::Vec2;
+use genetictool2::export::{self, ExportFormat};
+use genetictool2::params::random_seed;
+use genetictool2::{Direction, Encoding, FitnessTransform, RealCrossover, RealMutation, Representation};
+use genetictool2::{CrossoverMethod, Criterion, GenerationStats, Replacement, SelectionMethod, StopMonitor};
+use genetictool2::{objective, rng_from_seed, Engine, Objective, OptionsParams, VarSpec};
+
+use crate::render::{self, PlotFormat};
+use crate::{FunctionPlot, PlotColors, PlotOverlay};
+
+const USAGE: &str = "\
+Użycie: genetictool2 run [opcje]
+
+Opcje:
+  --generations N   liczba pokoleń do obliczenia (domyślnie 100)
+  --pop N           rozmiar populacji (POP_SIZE)
+  --mutation P      prawdopodobieństwo mutacji (MUTATION_PROB)
+  --crossover P     prawdopodobieństwo krzyżowania (CROSSOVER_PROB)
+  --crossover-op C  krzyżowanie bitowe: one-point | two-point | k-point | uniform | shuffle
+                    (domyślnie one-point)
+  --points K        liczba punktów cięcia dla k-point (domyślnie 3)
+  --swap-prob P     prawdopodobieństwo zamiany bitu dla uniform (domyślnie 0.5)
+  --tournament K    rozmiar turnieju (TOURNAMENT_K)
+  --selection S     selekcja: tournament | roulette | rank | sus | truncation | boltzmann
+                    (domyślnie tournament)
+  --rank-pressure S nacisk selekcji rankingowej, 1..2 (domyślnie 1.5)
+  --truncation T    ułamek najlepszych w selekcji obcięciowej (domyślnie 0.5)
+  --temperature T   temperatura selekcji Boltzmanna (domyślnie 1)
+  --elitism N       liczba elit przechodzących bez zmian (domyślnie 1)
+  --replacement R   zastępowanie: generational | plus | comma | steady
+                    (domyślnie generational)
+  --lambda N        liczba dzieci λ dla plus / comma (domyślnie 40)
+  --steady N        liczba zastępowanych osobników dla steady (domyślnie 2)
+  --target F        kończy, gdy najlepsze f osiągnie F (przy --minimize: spadnie do F)
+  --stagnation N    kończy po N pokoleniach bez poprawy najlepszego wyniku
+  --min-diversity D kończy, gdy rozrzut populacji spadnie poniżej D
+  --time-limit S    kończy po S sekundach
+  --seed S          ziarno generatora liczb losowych, 0..4294967295 (domyślnie losowe)
+  --function EXPR   funkcja celu, np. \"sin(x)*x^2\" albo \"x1^2 + x2^2 + x3^2\"
+                    (zmienne x, y, z albo x1..xN)
+  --preset NAME     wbudowana funkcja testowa: sphere | rastrigin | rosenbrock
+                    (wszystkie do minimalizacji – użyj z --minimize)
+  --dims N          liczba zmiennych dla --preset (domyślnie 2)
+  --x-min A         dolna granica dziedziny każdej zmiennej (domyślnie -10)
+  --x-max B         górna granica dziedziny każdej zmiennej (domyślnie 10)
+  --bits N          liczba bitów na zmienną, 4..64 (domyślnie 16)
+  --precision D     dobiera liczbę bitów tak, by uzyskać D cyfr po przecinku
+  --encoding E      kodowanie genomu: binary | gray (domyślnie binary)
+  --real            reprezentacja rzeczywista zamiast ciągu bitów
+  --real-crossover C
+                    krzyżowanie rzeczywiste: arithmetic | blx | sbx (domyślnie blx)
+  --real-mutation M mutacja rzeczywista: gaussian | polynomial (domyślnie gaussian)
+  --blx-alpha A     α krzyżowania BLX (domyślnie 0.5)
+  --sbx-eta E       η krzyżowania SBX (domyślnie 15)
+  --sigma S         σ mutacji gaussowskiej jako ułamek dziedziny (domyślnie 0.1)
+  --poly-eta E      η mutacji wielomianowej (domyślnie 20)
+  --minimize        szuka minimum zamiast maksimum funkcji celu
+  --transform T     przekształcenie fitness: none | offset | exp (domyślnie none)
+  --exp-scale C     współczynnik skalowania wykładniczego (domyślnie 1)
+  --export-population FILE
+                    zapisuje końcową populację (ranga, bity, x, f, fitness)
+                    do pliku .csv albo .json
+  --export-stats FILE
+                    zapisuje statystyki wszystkich pokoleń do pliku .csv albo .json
+  --plot FILE       zapisuje wykres funkcji z końcową populacją do pliku .svg albo .png
+  --plot-size WxH   rozmiar wykresu w punktach (domyślnie 1000x600)
+  --plot-scale S    powiększenie obrazu PNG, np. 2 = dwa razy więcej pikseli (domyślnie 1)
+  -h, --help        wyświetla tę pomoc";
+
+/// Ustawienia przebiegu wsadowego sparsowane z linii poleceń.
+struct RunArgs {
+    generations:       usize,
+    params:            OptionsParams,
+    objective:         Objective,
+    /// Plik eksportu końcowej populacji.
+    export_population: Option<(PathBuf, ExportFormat)>,
+    /// Plik eksportu statystyk kolejnych pokoleń.
+    export_stats:      Option<(PathBuf, ExportFormat)>,
+    /// Plik wykresu funkcji z końcową populacją.
+    plot:              Option<(PathBuf, PlotFormat)>,
+    plot_size:         Vec2,
+    plot_scale:        f32,
+}
+
+/// Punkt wejścia trybu wsadowego; zwraca kod wyjścia procesu.
+pub fn run(args: &[String]) -> i32 {
+    let run_args = match parse_args(args) {
+        Ok(Some(a)) => a,
+        Ok(None) => {
+            println!("{USAGE}");
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("błąd: {e}\n\n{USAGE}");
+            return 2;
+        }
+    };
+
+    let seed = run_args.params.seed;
+    let mut rng = rng_from_seed(seed);
+
+    let engine = Engine::new(run_args.objective);
+    let mut population = engine.random_population(&run_args.params, &mut rng);
+
+    println!("# seed={seed}");
+    println!("# f={}", engine.objective.label());
+    for (name, v) in engine.objective.vars().iter().zip(&run_args.params.vars) {
+        println!("# {name} in [{}, {}], {} bitów, rozdzielczość {:e}", v.min, v.max, v.bits, v.resolution());
+    }
+    let params = &run_args.params;
+    match params.representation {
+        Representation::Binary => println!("# {}, {}", params.representation.label(), params.encoding.label()),
+        Representation::Real   => println!("# {}", params.representation.label()),
+    }
+    println!("# {}", params.operators_summary());
+    println!("# {}", run_args.params.fitness.direction.label());
+    println!(
+        "{:>6} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "gen", "best", "mean", "median", "worst", "std"
+    );
+    let mut history = vec![GenerationStats::of(&population, &params.vars)];
+    print_stats(&history[0]);
+    let mut monitor = StopMonitor::new(&population, &params.fitness);
+    for _ in 0..run_args.generations {
+        engine.step(&mut population, params, &mut rng);
+        let stats = GenerationStats::of(&population, &params.vars);
+        print_stats(&stats);
+        history.push(stats);
+        if let Some(reason) = monitor.observe(&population, &params.stop, &params.fitness, &params.vars) {
+            println!("# stop: {reason}");
+            break;
+        }
+    }
+
+    let exports = [
+        run_args.export_population.map(|(path, format)| {
+            (path, export::population(&population, engine.objective.vars(), format))
+        }),
+        run_args.export_stats.map(|(path, format)| (path, export::stats(&history, format))),
+    ];
+    for (path, text) in exports.into_iter().flatten() {
+        if let Err(e) = export::save(&path, &text) {
+            eprintln!("błąd: {e}");
+            return 1;
+        }
+    }
+    if let Some((path, format)) = &run_args.plot {
+        let plot = FunctionPlot::new(engine.objective.clone(), &params.vars);
+        let overlay = PlotOverlay { population: Some(&population), marks: &[] };
+        let svg = plot.to_svg(run_args.plot_size, &PlotColors::default_light(), &overlay);
+        if let Err(e) = render::save_plot(path, *format, &svg, run_args.plot_scale) {
+            eprintln!("błąd: {e}");
+            return 1;
+        }
+    }
+    0
+}
+
+fn print_stats(s: &GenerationStats) {
+    println!(
+        "{:>6} {:>12.6} {:>12.6} {:>12.6} {:>12.6} {:>12.6}",
+        s.generation, s.best, s.mean, s.median, s.worst, s.std_dev
+    );
+}
+
+/// Zwraca `Ok(None)` gdy użytkownik poprosił o pomoc.
+fn parse_args(args: &[String]) -> Result<Option<RunArgs>, String> {
+    let mut run_args = RunArgs {
+        generations:       100,
+        params:            OptionsParams { seed: random_seed(), ..OptionsParams::default() },
+        objective:         objective::default_objective(),
+        export_population: None,
+        export_stats:      None,
+        plot:              None,
+        plot_size:         Vec2::new(1000.0, 600.0),
+        plot_scale:        1.0,
+    };
+
+    // Funkcja celu i dziedzina składane są po przeczytaniu wszystkich opcji,
+    // żeby kolejność --preset / --dims / --x-min nie miała znaczenia.
+    let mut function: Option<String> = None;
+    let mut preset: Option<String> = None;
+    let mut dims = 2;
+    let mut domain = VarSpec::default();
+    let mut precision: Option<u32> = None;
+
+    let mut it = args.iter();
+    while let Some(flag) = it.next() {
+        if flag == "-h" || flag == "--help" {
+            return Ok(None);
+        }
+        if flag == "--minimize" {
+            run_args.params.fitness.direction = Direction::Minimize;
+            continue;
+        }
+        if flag == "--real" {
+            run_args.params.representation = Representation::Real;
+            continue;
+        }
+        let value = it.next().ok_or_else(|| format!("brak wartości dla {flag}"))?;
+        match flag.as_str() {
+            "--generations" => run_args.generations = parse_value(flag, value)?,
+            "--pop"         => run_args.params.pop_size = parse_value(flag, value)?,
+            "--mutation"    => run_args.params.mutation_prob = parse_value(flag, value)?,
+            "--crossover"   => run_args.params.crossover_prob = parse_value(flag, value)?,
+            "--tournament"  => run_args.params.tournament_k = parse_value(flag, value)?,
+            "--crossover-op" => run_args.params.crossover.method = parse_crossover(value)?,
+            "--points"      => run_args.params.crossover.points = parse_value(flag, value)?,
+            "--swap-prob"   => run_args.params.crossover.swap_prob = parse_value(flag, value)?,
+            "--elitism"     => run_args.params.elitism = parse_value(flag, value)?,
+            "--replacement" => run_args.params.replacement.strategy = parse_replacement(value)?,
+            "--lambda"      => run_args.params.replacement.lambda = parse_value(flag, value)?,
+            "--steady"      => run_args.params.replacement.steady_count = parse_value(flag, value)?,
+            "--selection"   => run_args.params.selection.method = parse_selection(value)?,
+            "--rank-pressure" => run_args.params.selection.rank_pressure = parse_value(flag, value)?,
+            "--truncation"  => run_args.params.selection.truncation = parse_value(flag, value)?,
+            "--temperature" => run_args.params.selection.temperature = parse_value(flag, value)?,
+            "--target"      => run_args.params.stop.target_value = enabled(parse_value(flag, value)?),
+            "--stagnation"  => run_args.params.stop.stagnation = enabled(parse_value(flag, value)?),
+            "--min-diversity" => run_args.params.stop.min_diversity = enabled(parse_value(flag, value)?),
+            "--time-limit"  => run_args.params.stop.time_limit = enabled(parse_value(flag, value)?),
+            "--seed"        => run_args.params.seed = parse_value::<u32>(flag, value)?.into(),
+            "--transform"   => run_args.params.fitness.transform = parse_transform(value)?,
+            "--exp-scale"   => run_args.params.fitness.exp_scale = parse_value(flag, value)?,
+            "--x-min"       => domain.min = parse_value(flag, value)?,
+            "--x-max"       => domain.max = parse_value(flag, value)?,
+            "--dims"        => dims = parse_value(flag, value)?,
+            "--bits"        => domain.bits = parse_value(flag, value)?,
+            "--precision"   => precision = Some(parse_value(flag, value)?),
+            "--encoding"    => run_args.params.encoding = parse_encoding(value)?,
+            "--real-crossover" => run_args.params.real.crossover = parse_real_crossover(value)?,
+            "--real-mutation"  => run_args.params.real.mutation = parse_real_mutation(value)?,
+            "--blx-alpha"   => run_args.params.real.blx_alpha = parse_value(flag, value)?,
+            "--sbx-eta"     => run_args.params.real.sbx_eta = parse_value(flag, value)?,
+            "--sigma"       => run_args.params.real.gauss_sigma = parse_value(flag, value)?,
+            "--poly-eta"    => run_args.params.real.poly_eta = parse_value(flag, value)?,
+            "--function"    => function = Some(value.clone()),
+            "--preset"      => preset = Some(value.clone()),
+            "--export-population" => run_args.export_population = Some(parse_export(value)?),
+            "--export-stats" => run_args.export_stats = Some(parse_export(value)?),
+            "--plot"        => run_args.plot = Some(parse_plot(value)?),
+            "--plot-size"   => run_args.plot_size = parse_plot_size(value)?,
+            "--plot-scale"  => run_args.plot_scale = parse_value(flag, value)?,
+            _ => return Err(format!("nieznana opcja {flag}")),
+        }
+    }
+
+    run_args.objective = match (function, preset) {
+        (Some(_), Some(_)) => return Err("--function i --preset wykluczają się".into()),
+        (Some(src), None) => Objective::parse(&src)
+            .map_err(|e| format!("niepoprawna funkcja celu ({e})"))?,
+        (None, Some(name)) => {
+            if dims < 1 {
+                return Err("--dims musi wynosić co najmniej 1".into());
+            }
+            objective::preset(&name, dims)
+                .ok_or_else(|| format!("nieznana funkcja testowa {name:?}"))?
+        }
+        (None, None) => objective::default_objective(),
+    };
+    if let Some(decimals) = precision {
+        domain.bits = domain.bits_for_precision(decimals);
+    }
+    run_args.params.vars = vec![domain; run_args.objective.dims()];
+
+    if !(run_args.plot_scale > 0.0 && run_args.plot_scale <= 16.0) {
+        return Err("--plot-scale musi należeć do przedziału (0, 16]".into());
+    }
+
+    run_args.params.validate()?;
+    Ok(Some(run_args))
+}
+
+fn parse_replacement(value: &str) -> Result<Replacement, String> {
+    match value {
+        "generational" => Ok(Replacement::Generational),
+        "plus"         => Ok(Replacement::MuPlusLambda),
+        "comma"        => Ok(Replacement::MuCommaLambda),
+        "steady"       => Ok(Replacement::SteadyState),
+        _ => Err(format!("nieznane zastępowanie {value:?} (generational | plus | comma | steady)")),
+    }
+}
+
+fn parse_crossover(value: &str) -> Result<CrossoverMethod, String> {
+    match value {
+        "one-point" => Ok(CrossoverMethod::OnePoint),
+        "two-point" => Ok(CrossoverMethod::TwoPoint),
+        "k-point"   => Ok(CrossoverMethod::KPoint),
+        "uniform"   => Ok(CrossoverMethod::Uniform),
+        "shuffle"   => Ok(CrossoverMethod::Shuffle),
+        _ => Err(format!(
+            "nieznane krzyżowanie {value:?} (one-point | two-point | k-point | uniform | shuffle)"
+        )),
+    }
+}
+
+fn parse_selection(value: &str) -> Result<SelectionMethod, String> {
+    match value {
+        "tournament" => Ok(SelectionMethod::Tournament),
+        "roulette"   => Ok(SelectionMethod::Roulette),
+        "rank"       => Ok(SelectionMethod::Rank),
+        "sus"        => Ok(SelectionMethod::Sus),
+        "truncation" => Ok(SelectionMethod::Truncation),
+        "boltzmann"  => Ok(SelectionMethod::Boltzmann),
+        _ => Err(format!(
+            "nieznana selekcja {value:?} (tournament | roulette | rank | sus | truncation | boltzmann)"
+        )),
+    }
+}
+
+fn parse_encoding(value: &str) -> Result<Encoding, String> {
+    match value {
+        "binary" => Ok(Encoding::Binary),
+        "gray"   => Ok(Encoding::Gray),
+        _ => Err(format!("nieznane kodowanie {value:?} (binary | gray)")),
+    }
+}
+
+fn parse_real_crossover(value: &str) -> Result<RealCrossover, String> {
+    match value {
+        "arithmetic" => Ok(RealCrossover::Arithmetic),
+        "blx"        => Ok(RealCrossover::Blx),
+        "sbx"        => Ok(RealCrossover::Sbx),
+        _ => Err(format!("nieznane krzyżowanie {value:?} (arithmetic | blx | sbx)")),
+    }
+}
+
+fn parse_real_mutation(value: &str) -> Result<RealMutation, String> {
+    match value {
+        "gaussian"   => Ok(RealMutation::Gaussian),
+        "polynomial" => Ok(RealMutation::Polynomial),
+        _ => Err(format!("nieznana mutacja {value:?} (gaussian | polynomial)")),
+    }
+}
+
+fn parse_transform(value: &str) -> Result<FitnessTransform, String> {
+    match value {
+        "none"   => Ok(FitnessTransform::Identity),
+        "offset" => Ok(FitnessTransform::Offset),
+        "exp"    => Ok(FitnessTransform::Exponential),
+        _ => Err(format!("nieznane przekształcenie fitness {value:?} (none | offset | exp)")),
+    }
+}
+
+// Plik eksportu – format wynika z rozszerzenia.
+fn parse_export(value: &str) -> Result<(PathBuf, ExportFormat), String> {
+    let path = PathBuf::from(value);
+    let format = ExportFormat::from_path(&path)?;
+    Ok((path, format))
+}
+
+// Plik wykresu – format wynika z rozszerzenia.
+fn parse_plot(value: &str) -> Result<(PathBuf, PlotFormat), String> {
+    let path = PathBuf::from(value);
+    let format = PlotFormat::from_path(&path)?;
+    Ok((path, format))
+}
+
+// Rozmiar wykresu "SZEROKOŚĆxWYSOKOŚĆ", np. "1000x600".
+fn parse_plot_size(value: &str) -> Result<Vec2, String> {
+    let invalid = || format!("niepoprawny rozmiar wykresu {value:?} (np. 1000x600)");
+    let (w, h) = value.split_once('x').ok_or_else(invalid)?;
+    let (w, h): (u32, u32) = (w.parse().map_err(|_| invalid())?, h.parse().map_err(|_| invalid())?);
+    if !(100..=10_000).contains(&w) || !(100..=10_000).contains(&h) {
+        return Err("--plot-size: każdy wymiar musi mieścić się w 100..10000".into());
+    }
+    Ok(Vec2::new(w as f32, h as f32))
+}
+
+fn enabled<T>(value: T) -> Criterion<T> {
+    Criterion { enabled: true, value }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("niepoprawna wartość {value:?} dla {flag}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<RunArgs>, String> {
+        parse_args(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn help_returns_none() {
+        assert!(matches!(parse(&["--help"]), Ok(None)));
+    }
+
+    #[test]
+    fn options_are_applied() {
+        let a = parse(&["--generations", "7", "--pop", "30", "--seed", "9", "--minimize"]).unwrap().unwrap();
+        assert_eq!(a.generations, 7);
+        assert_eq!(a.params.pop_size, 30);
+        assert_eq!(a.params.seed, 9);
+        assert_eq!(a.params.fitness.direction, Direction::Minimize);
+    }
+
+    #[test]
+    fn preset_sets_dimensions_regardless_of_flag_order() {
+        let a = parse(&["--x-min", "-5", "--preset", "sphere", "--dims", "3"]).unwrap().unwrap();
+        assert_eq!(a.objective.dims(), 3);
+        assert_eq!(a.params.vars.len(), 3);
+        assert!(a.params.vars.iter().all(|v| v.min == -5.0));
+    }
+
+    #[test]
+    fn invalid_input_is_rejected() {
+        assert!(parse(&["--bogus", "1"]).is_err());
+        assert!(parse(&["--pop"]).is_err());
+        assert!(parse(&["--pop", "abc"]).is_err());
+        assert!(parse(&["--function", "x", "--preset", "sphere"]).is_err());
+        assert!(parse(&["--export-stats", "wyniki.xlsx"]).is_err());
+        assert!(parse(&["--seed", "4294967296"]).is_err());
+    }
+}
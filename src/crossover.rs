@@ -0,0 +1,266 @@
+// ---------------------------------------------------------------------------
+// Krzyżowanie genomów bitowych
+//
+// Każdy operator implementuje [`Crossover`]: losuje maskę zamian – bity,
+// które pierwsze dziecko bierze od drugiego rodzica (i odwrotnie). Drugie
+// dziecko dostaje więc dokładnie te fragmenty, których nie dostało pierwsze,
+// i żaden gen rodziców nie ginie. Z maski da się też odczytać punkty cięcia
+// (patrz `crate::genealogy`).
+// Przy wielu zmiennych cięcia padają w dowolnym miejscu sklejonego genomu,
+// więc mogą wypaść także w środku odcinka jednej zmiennej.
+// ---------------------------------------------------------------------------
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Wspólny interfejs operatorów krzyżowania genomu bitowego.
+pub trait Crossover {
+    /// Losuje maskę zamian dla genomu długości `len`: `true` na pozycjach,
+    /// które dzieci wymieniają między sobą.
+    fn mask(&self, len: usize, rng: &mut dyn RngCore) -> Vec<bool>;
+
+    /// Krzyżuje rodziców `a` i `b` (tej samej długości); zwraca dwoje dzieci.
+    fn cross(&self, a: &[bool], b: &[bool], rng: &mut dyn RngCore) -> (Vec<bool>, Vec<bool>) {
+        apply_mask(a, b, &self.mask(a.len(), rng))
+    }
+}
+
+/// Dwoje dzieci rodziców `a` i `b` według maski zamian.
+pub fn apply_mask(a: &[bool], b: &[bool], mask: &[bool]) -> (Vec<bool>, Vec<bool>) {
+    let (mut child_a, mut child_b) = (a.to_vec(), b.to_vec());
+    for (i, _) in mask.iter().enumerate().filter(|&(_, &swap)| swap) {
+        child_a[i] = b[i];
+        child_b[i] = a[i];
+    }
+    (child_a, child_b)
+}
+
+/// Operator krzyżowania wybierany w oknie opcji.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrossoverMethod {
+    #[default]
+    OnePoint,
+    TwoPoint,
+    KPoint,
+    Uniform,
+    Shuffle,
+}
+
+impl CrossoverMethod {
+    pub const ALL: [CrossoverMethod; 5] = [
+        CrossoverMethod::OnePoint,
+        CrossoverMethod::TwoPoint,
+        CrossoverMethod::KPoint,
+        CrossoverMethod::Uniform,
+        CrossoverMethod::Shuffle,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CrossoverMethod::OnePoint => "Jednopunktowe",
+            CrossoverMethod::TwoPoint => "Dwupunktowe",
+            CrossoverMethod::KPoint   => "K-punktowe",
+            CrossoverMethod::Uniform  => "Jednorodne",
+            CrossoverMethod::Shuffle  => "Tasujące",
+        }
+    }
+}
+
+/// Operator krzyżowania wraz z parametrami.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrossoverParams {
+    pub method:    CrossoverMethod,
+    /// Liczba punktów cięcia krzyżowania K-punktowego.
+    pub points:    usize,
+    /// Prawdopodobieństwo zamiany bitu w krzyżowaniu jednorodnym.
+    pub swap_prob: f64,
+}
+
+impl Default for CrossoverParams {
+    fn default() -> Self {
+        Self {
+            method:    CrossoverMethod::OnePoint,
+            points:    3,
+            swap_prob: 0.5,
+        }
+    }
+}
+
+impl CrossoverParams {
+    /// Sprawdza parametry krzyżowania; zwraca opis pierwszego błędu.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.points < 1 {
+            return Err("krzyżowanie K-punktowe wymaga co najmniej 1 punktu".into());
+        }
+        if !(0.0..=1.0).contains(&self.swap_prob) {
+            return Err("prawdopodobieństwo zamiany bitu musi należeć do [0, 1]".into());
+        }
+        Ok(())
+    }
+
+    /// Tworzy obiekt krzyżowania dla wybranej metody.
+    pub fn operator(&self) -> Box<dyn Crossover> {
+        match self.method {
+            CrossoverMethod::OnePoint => Box::new(KPoint { points: 1 }),
+            CrossoverMethod::TwoPoint => Box::new(KPoint { points: 2 }),
+            CrossoverMethod::KPoint   => Box::new(KPoint { points: self.points }),
+            CrossoverMethod::Uniform  => Box::new(Uniform { swap_prob: self.swap_prob }),
+            CrossoverMethod::Shuffle  => Box::new(Shuffle),
+        }
+    }
+}
+
+// -- Krzyżowanie K-punktowe (w tym jedno- i dwupunktowe) ---------------------
+// Wybieramy K losowych punktów cięcia i na przemian bierzemy kawałki
+// od jednego i drugiego rodzica.
+// Np. jednopunktowe: rodzic A: 1101|0011  rodzic B: 0010|1100
+//                    dzieci:   1101|1100            0010|0011
+// Np. dwupunktowe:   rodzic A: 11|0100|11  rodzic B: 00|1011|00
+//                    dzieci:   11|1011|11            00|0100|00
+pub struct KPoint {
+    pub points: usize,
+}
+
+impl Crossover for KPoint {
+    fn mask(&self, len: usize, rng: &mut dyn RngCore) -> Vec<bool> {
+        let mut mask = vec![false; len];
+        if len < 2 {
+            return mask;
+        }
+        // Punkty cięcia: różne, z zakresu 1..len-1, rosnąco.
+        let k = self.points.min(len - 1);
+        let mut cuts: Vec<usize> = rand::seq::index::sample(rng, len - 1, k)
+            .into_iter()
+            .map(|i| i + 1)
+            .collect();
+        cuts.sort_unstable();
+
+        // Co drugi odcinek (licząc od pierwszego cięcia) zamieniamy między dziećmi.
+        let mut swapped = false;
+        let mut start = 0;
+        for end in cuts.into_iter().chain(std::iter::once(len)) {
+            mask[start..end].fill(swapped);
+            swapped = !swapped;
+            start = end;
+        }
+        mask
+    }
+}
+
+// -- Krzyżowanie jednorodne --------------------------------------------------
+// Każdy bit osobno: z prawdopodobieństwem p zamieniamy go między dziećmi.
+// Przy p = 0.5 każde dziecko to "rzut monetą" dla każdego genu.
+pub struct Uniform {
+    pub swap_prob: f64,
+}
+
+impl Crossover for Uniform {
+    fn mask(&self, len: usize, rng: &mut dyn RngCore) -> Vec<bool> {
+        (0..len).map(|_| rng.gen_bool(self.swap_prob)).collect()
+    }
+}
+
+// -- Krzyżowanie tasujące ----------------------------------------------------
+// Przed krzyżowaniem jednopunktowym tasujemy pozycje bitów (tą samą
+// permutacją u obu rodziców), a po nim przywracamy kolejność. Dzięki temu
+// wynik nie zależy od tego, czy geny leżą w genomie blisko siebie.
+pub struct Shuffle;
+
+impl Crossover for Shuffle {
+    fn mask(&self, len: usize, rng: &mut dyn RngCore) -> Vec<bool> {
+        let mut perm: Vec<usize> = (0..len).collect();
+        perm.shuffle(rng);
+        // Maska jednopunktowa w kolejności potasowanej, przeniesiona
+        // z powrotem na oryginalne pozycje bitów.
+        let shuffled = KPoint { points: 1 }.mask(len, rng);
+        let mut mask = vec![false; len];
+        for (pos, &i) in perm.iter().enumerate() {
+            mask[i] = shuffled[pos];
+        }
+        mask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genealogy::Origin;
+    use crate::rng_from_seed;
+
+    fn operators() -> Vec<Box<dyn Crossover>> {
+        let mut ops: Vec<Box<dyn Crossover>> = CrossoverMethod::ALL
+            .iter()
+            .map(|&method| CrossoverParams { method, ..CrossoverParams::default() }.operator())
+            .collect();
+        ops.extend([Box::new(KPoint { points: 7 }) as Box<dyn Crossover>, Box::new(Uniform { swap_prob: 0.9 })]);
+        ops
+    }
+
+    #[test]
+    fn children_together_hold_exactly_the_parents_genes() {
+        let mut rng = rng_from_seed(1);
+        for op in operators() {
+            for len in [0, 1, 2, 3, 16, 65] {
+                for _ in 0..20 {
+                    let a: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+                    let b: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+                    let (ca, cb) = op.cross(&a, &b, &mut rng);
+                    assert_eq!((ca.len(), cb.len()), (len, len));
+                    for i in 0..len {
+                        let from_a = (ca[i], cb[i]) == (a[i], b[i]);
+                        let swapped = (ca[i], cb[i]) == (b[i], a[i]);
+                        assert!(from_a || swapped, "len = {len}, i = {i}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn k_point_makes_k_distinct_cuts() {
+        let mut rng = rng_from_seed(2);
+        for k in 1..=5 {
+            for _ in 0..20 {
+                let mask = KPoint { points: k }.mask(32, &mut rng);
+                assert_eq!(Origin::cut_points(&mask).len(), k);
+                assert!(!mask[0], "pierwszy odcinek zostaje u swojego rodzica");
+            }
+        }
+    }
+
+    #[test]
+    fn k_point_with_k_at_least_genome_length_cuts_everywhere() {
+        let mut rng = rng_from_seed(3);
+        for k in [5, 6, 100] {
+            let mask = KPoint { points: k }.mask(5, &mut rng);
+            assert_eq!(mask, [false, true, false, true, false]);
+        }
+        assert_eq!(KPoint { points: 3 }.mask(1, &mut rng), [false]);
+        assert!(KPoint { points: 3 }.mask(0, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn shuffle_mask_swaps_a_proper_subset() {
+        let mut rng = rng_from_seed(4);
+        for _ in 0..50 {
+            let swapped = Shuffle.mask(20, &mut rng).iter().filter(|&&s| s).count();
+            assert!((1..20).contains(&swapped));
+        }
+    }
+
+    #[test]
+    fn uniform_extremes_copy_or_swap_parents() {
+        let mut rng = rng_from_seed(5);
+        assert!(Uniform { swap_prob: 0.0 }.mask(16, &mut rng).iter().all(|&s| !s));
+        assert!(Uniform { swap_prob: 1.0 }.mask(16, &mut rng).iter().all(|&s| s));
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        assert_eq!(CrossoverParams::default().validate(), Ok(()));
+        assert!(CrossoverParams { points: 0, ..CrossoverParams::default() }.validate().is_err());
+        assert!(CrossoverParams { swap_prob: 1.5, ..CrossoverParams::default() }.validate().is_err());
+    }
+}
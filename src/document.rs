@@ -0,0 +1,255 @@
+// ---------------------------------------------------------------------------
+// Zapis i odczyt przebiegu
+//
+// Przebieg zapisywany jest jako dokument TOML: parametry GA (z dziedziną
+// i ziarnem), tekst funkcji celu, bieżąca populacja, stan generatora liczb
+// losowych i – opcjonalnie – historia statystyk. Stan generatora sprawia,
+// że wczytany przebieg liczy się dalej dokładnie tak, jakby go nie przerywano.
+//
+// Pole `version` pozwala rozpoznać pliki zapisane przez inne wersje programu;
+// TOML zapisuje też wartości NaN i nieskończone (np. fitness niepoprawnych
+// osobników), których nie da się zapisać w JSON.
+// ---------------------------------------------------------------------------
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::{genome_len, Representation};
+use crate::engine::GaRng;
+use crate::objective::Objective;
+use crate::params::OptionsParams;
+use crate::population::Population;
+use crate::stats::GenerationStats;
+
+/// Wersja formatu zapisywana w każdym pliku przebiegu.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Rozszerzenie plików przebiegu.
+pub const EXTENSION: &str = "toml";
+
+/// Zapisany przebieg GA.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RunDocument {
+    /// Wersja formatu (patrz [`FORMAT_VERSION`]).
+    pub version:    u32,
+    /// Tekst wyrażenia funkcji celu.
+    pub objective:  String,
+    /// Ziarno, z którego wystartował przebieg (`params.seed` mogło się
+    /// od tego czasu zmienić w opcjach).
+    pub run_seed:   u64,
+    /// Parametry GA, w tym dziedzina (`vars`) i ziarno następnego przebiegu.
+    pub params:     OptionsParams,
+    /// Stan generatora liczb losowych po ostatnim pokoleniu.
+    pub rng:        RngState,
+    /// Bieżąca populacja.
+    pub population: Population,
+    /// Statystyki kolejnych pokoleń (pomijane, gdy zapis bez historii).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history:    Option<Vec<GenerationStats>>,
+}
+
+/// Stan generatora ChaCha: klucz (ziarno), numer strumienia i pozycja w nim.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RngState {
+    /// 32 bajty klucza zapisane szesnastkowo.
+    pub key:    String,
+    pub stream: u64,
+    /// Pozycja w strumieniu (w słowach 32-bitowych).
+    pub word:   u64,
+}
+
+impl RngState {
+    pub fn of(rng: &GaRng) -> Self {
+        let key = rng.get_seed().iter().map(|b| format!("{b:02x}")).collect();
+        Self { key, stream: rng.get_stream(), word: rng.get_word_pos() as u64 }
+    }
+
+    /// Odtwarza generator w zapisanym stanie.
+    pub fn restore(&self) -> Result<GaRng, String> {
+        use rand::SeedableRng;
+
+        let bytes = self.key.as_bytes();
+        if bytes.len() != 64 {
+            return Err("klucz generatora musi mieć 64 cyfry szesnastkowe".into());
+        }
+        let mut seed = [0u8; 32];
+        for (byte, pair) in seed.iter_mut().zip(bytes.chunks(2)) {
+            let digits = std::str::from_utf8(pair).map_err(|_| "niepoprawny klucz generatora")?;
+            *byte = u8::from_str_radix(digits, 16).map_err(|_| "niepoprawny klucz generatora")?;
+        }
+        let mut rng = GaRng::from_seed(seed);
+        rng.set_stream(self.stream);
+        rng.set_word_pos(self.word as u128);
+        Ok(rng)
+    }
+}
+
+impl RunDocument {
+    /// Dokument bieżącego przebiegu; `history` = `None` zapisuje sam stan.
+    pub fn new(
+        objective: &Objective,
+        params: &OptionsParams,
+        run_seed: u64,
+        rng: &GaRng,
+        population: &Population,
+        history: Option<&[GenerationStats]>,
+    ) -> Self {
+        Self {
+            version:    FORMAT_VERSION,
+            objective:  objective.label().to_string(),
+            run_seed,
+            params:     params.clone(),
+            rng:        RngState::of(rng),
+            population: population.clone(),
+            history:    history.map(<[GenerationStats]>::to_vec),
+        }
+    }
+
+    /// Tekst dokumentu w formacie TOML.
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string(self).map_err(|e| format!("nie udało się zapisać przebiegu: {e}"))
+    }
+
+    /// Wczytuje dokument i sprawdza, czy opisuje spójny przebieg.
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        // Najpierw sama wersja – plik z nowszej wersji programu może mieć
+        // pola, których ta wersja nie zna.
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+        let Version { version } = toml::from_str(text).map_err(|e| format!("to nie jest plik przebiegu: {e}"))?;
+        if version != FORMAT_VERSION {
+            return Err(format!("nieobsługiwana wersja pliku: {version} (obsługiwana: {FORMAT_VERSION})"));
+        }
+        let mut doc: Self = toml::from_str(text).map_err(|e| format!("błąd w pliku przebiegu: {e}"))?;
+        doc.validate()?;
+        // Fitness wynika z wartości i trybu – przeliczenie przywraca też sortowanie.
+        doc.population.rescore(&doc.params.fitness);
+        Ok(doc)
+    }
+
+    /// Zapisuje dokument do pliku.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_toml()?).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Wczytuje dokument z pliku.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Self::from_toml(&text)
+    }
+
+    /// Funkcja celu zapisana w dokumencie.
+    pub fn objective(&self) -> Result<Objective, String> {
+        Objective::parse(&self.objective).map_err(|e| format!("funkcja celu: {}", e.msg))
+    }
+
+    /// Sprawdza parametry, funkcję celu, generator i zgodność populacji z dziedziną.
+    fn validate(&self) -> Result<(), String> {
+        self.params.validate()?;
+        if self.objective()?.dims() != self.params.vars.len() {
+            return Err("liczba zmiennych funkcji celu nie zgadza się z dziedziną".into());
+        }
+        self.rng.restore()?;
+        if self.population.chromosomes.is_empty() {
+            return Err("populacja jest pusta".into());
+        }
+        let len = genome_len(&self.params.vars);
+        for (i, c) in self.population.chromosomes.iter().enumerate() {
+            let genes_ok = match self.params.representation {
+                Representation::Binary => c.genes.len() == len,
+                Representation::Real   => c.genes.is_empty(),
+            };
+            if !genes_ok || c.xs.len() != self.params.vars.len() {
+                return Err(format!("osobnik {}: genom nie pasuje do dziedziny", i + 1));
+            }
+        }
+        let generation = self.population.generation;
+        if let Some(history) = &self.history
+            && history.last().is_some_and(|s| s.generation != generation)
+        {
+            return Err("historia statystyk nie kończy się na bieżącym pokoleniu".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{rng_from_seed, Engine};
+
+    /// Przebieg po `generations` pokoleniach: silnik, populacja, generator i statystyki.
+    fn run(source: &str, generations: usize) -> (Engine, OptionsParams, Population, GaRng, Vec<GenerationStats>) {
+        let engine = Engine::new(Objective::parse(source).unwrap());
+        let params = OptionsParams { elitism: 1, ..OptionsParams::default() };
+        let mut rng = rng_from_seed(params.seed);
+        let mut pop = engine.random_population(&params, &mut rng);
+        let mut stats = vec![GenerationStats::of(&pop, &params.vars)];
+        for _ in 0..generations {
+            engine.step(&mut pop, &params, &mut rng);
+            stats.push(GenerationStats::of(&pop, &params.vars));
+        }
+        (engine, params, pop, rng, stats)
+    }
+
+    fn genomes(pop: &Population) -> Vec<(Vec<bool>, u64)> {
+        pop.chromosomes.iter().map(|c| (c.genes.clone(), c.origin.id)).collect()
+    }
+
+    #[test]
+    fn restored_run_continues_like_an_uninterrupted_one() {
+        // ln(x) daje NaN dla połowy dziedziny – TOML musi je zachować.
+        for source in ["(x + 5) * cos(2*x - 5) - 5", "ln(x)"] {
+            let (engine, params, pop, rng, stats) = run(source, 6);
+            let doc = RunDocument::new(&engine.objective, &params, params.seed, &rng, &pop, Some(&stats));
+            let loaded = RunDocument::from_toml(&doc.to_toml().unwrap()).unwrap();
+            assert_eq!(loaded.history.as_ref().map(Vec::len), Some(7));
+            assert_eq!(genomes(&loaded.population), genomes(&pop));
+
+            let (mut expected, mut expected_rng) = (pop, rng);
+            engine.step(&mut expected, &params, &mut expected_rng);
+
+            let restored = Engine::new(loaded.objective().unwrap());
+            let (mut pop, mut rng) = (loaded.population, loaded.rng.restore().unwrap());
+            restored.step(&mut pop, &loaded.params, &mut rng);
+            assert_eq!(pop.generation, 7);
+            assert_eq!(genomes(&pop), genomes(&expected), "{source}");
+        }
+    }
+
+    #[test]
+    fn other_format_versions_are_rejected() {
+        let (engine, params, pop, rng, _) = run("x", 1);
+        let text = RunDocument::new(&engine.objective, &params, 1, &rng, &pop, None).to_toml().unwrap();
+        assert!(text.contains(&format!("version = {FORMAT_VERSION}")));
+        let newer = text.replace(&format!("version = {FORMAT_VERSION}"), "version = 99");
+        assert!(RunDocument::from_toml(&newer).unwrap_err().contains("wersja"));
+        assert!(RunDocument::from_toml("objective = \"x\"").is_err());
+    }
+
+    #[test]
+    fn inconsistent_documents_are_rejected() {
+        let (engine, params, pop, rng, stats) = run("x", 2);
+        let doc = RunDocument::new(&engine.objective, &params, 1, &rng, &pop, Some(&stats));
+
+        let wrong_dims = RunDocument { objective: "x + y".into(), ..doc.clone() };
+        assert!(RunDocument::from_toml(&wrong_dims.to_toml().unwrap()).is_err());
+
+        let short_history = RunDocument { history: Some(stats[..2].to_vec()), ..doc.clone() };
+        assert!(RunDocument::from_toml(&short_history.to_toml().unwrap()).is_err());
+
+        let bad_key = RunDocument { rng: RngState { key: "zz".repeat(32), ..doc.rng.clone() }, ..doc };
+        assert!(RunDocument::from_toml(&bad_key.to_toml().unwrap()).is_err());
+    }
+
+    #[test]
+    fn rng_state_round_trip() {
+        let mut rng = rng_from_seed(42);
+        rand::RngCore::next_u64(&mut rng);
+        let mut restored = RngState::of(&rng).restore().unwrap();
+        assert_eq!(rand::RngCore::next_u64(&mut restored), rand::RngCore::next_u64(&mut rng));
+    }
+}
@@ -0,0 +1,114 @@
+// ---------------------------------------------------------------------------
+// Kodowanie odcinka genomu na liczbę całkowitą
+//
+// Każda zmienna zajmuje w genomie odcinek `bits` bitów, który najpierw
+// zamieniany jest na liczbę całkowitą n ∈ [0, 2^bits), a dopiero potem
+// mapowany liniowo na dziedzinę [min, max].
+//
+//   - Binary – zwykły zapis dwójkowy. Sąsiednie wartości mogą różnić się
+//              wieloma bitami ("urwisko Hamminga"): 0111 → 1000 to o 1 w górę,
+//              ale mutacja musiałaby odwrócić wszystkie cztery bity naraz.
+//   - Gray   – odbity kod Graya: sąsiednie wartości różnią się zawsze
+//              dokładnie jednym bitem, więc pojedyncza mutacja może przesunąć
+//              osobnika o jeden krok w dowolną stronę.
+//
+// Losowe bity dają jednostajny rozkład n w obu kodowaniach (kod Graya jest
+// permutacją [0, 2^bits)), więc populacja startowa jest losowana tak samo –
+// różni się tylko jej dekodowanie.
+// ---------------------------------------------------------------------------
+
+use serde::{Deserialize, Serialize};
+
+/// Sposób zamiany odcinka bitów genomu na liczbę całkowitą.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Encoding {
+    #[default]
+    Binary,
+    Gray,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 2] = [Encoding::Binary, Encoding::Gray];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Encoding::Binary => "Binarne",
+            Encoding::Gray   => "Kod Graya",
+        }
+    }
+
+    /// Liczba całkowita zapisana w odcinku (najstarszy bit pierwszy).
+    pub fn decode_segment(self, segment: &[bool]) -> u64 {
+        let raw = segment.iter().fold(0u64, |acc, &b| (acc << 1) | b as u64);
+        match self {
+            Encoding::Binary => raw,
+            Encoding::Gray   => gray_to_binary(raw),
+        }
+    }
+}
+
+/// Kod Graya liczby `n` (sąsiednie liczby różnią się jednym bitem).
+pub fn binary_to_gray(n: u64) -> u64 {
+    n ^ (n >> 1)
+}
+
+/// Liczba zapisana kodem Graya – odwrotność [`binary_to_gray`].
+///
+/// Bit i liczby binarnej to XOR wszystkich bitów kodu Graya od najstarszego
+/// do i-tego – przesuwamy o 1, 2, 4, … i sumujemy XOR-em (log2(64) = 6 kroków).
+pub fn gray_to_binary(gray: u64) -> u64 {
+    let mut n = gray;
+    let mut shift = 1;
+    while shift < 64 {
+        n ^= n >> shift;
+        shift <<= 1;
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromosome::{Chromosome, VarSpec, MAX_BITS};
+
+    fn bits(n: u64, width: usize) -> Vec<bool> {
+        (0..width).rev().map(|i| (n >> i) & 1 == 1).collect()
+    }
+
+    #[test]
+    fn gray_conversions_are_inverse() {
+        let samples = (0..1024).chain([u64::MAX, u64::MAX - 1, 1 << 63, 0x5555_5555_5555_5555]);
+        for n in samples {
+            assert_eq!(gray_to_binary(binary_to_gray(n)), n, "n = {n}");
+            assert_eq!(binary_to_gray(gray_to_binary(n)), n, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn gray_neighbours_differ_by_one_bit() {
+        for n in 0..1024u64 {
+            assert_eq!((binary_to_gray(n) ^ binary_to_gray(n + 1)).count_ones(), 1, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn decode_segment_reads_most_significant_bit_first() {
+        assert_eq!(Encoding::Binary.decode_segment(&bits(0b0110, 4)), 0b0110);
+        assert_eq!(Encoding::Gray.decode_segment(&bits(binary_to_gray(11), 4)), 11);
+        assert_eq!(Encoding::Binary.decode_segment(&[]), 0);
+    }
+
+    #[test]
+    fn full_width_segments_decode_to_domain_edges() {
+        let vars = [VarSpec { min: -3.0, max: 5.0, bits: MAX_BITS }];
+        for encoding in Encoding::ALL {
+            let encode = |n: u64| match encoding {
+                Encoding::Binary => bits(n, MAX_BITS),
+                Encoding::Gray   => bits(binary_to_gray(n), MAX_BITS),
+            };
+            assert_eq!(encoding.decode_segment(&encode(u64::MAX)), u64::MAX);
+            assert_eq!(Chromosome::decode(&encode(0), &vars, encoding), [-3.0], "{encoding:?}");
+            assert_eq!(Chromosome::decode(&encode(u64::MAX), &vars, encoding), [5.0], "{encoding:?}");
+        }
+    }
+}
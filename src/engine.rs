@@ -0,0 +1,346 @@
+use rand::{Rng, SeedableRng};
+
+use crate::chromosome::{Chromosome, Representation};
+use crate::crossover::apply_mask;
+use crate::fitness::FitnessMode;
+use crate::genealogy::Origin;
+use crate::objective::Objective;
+use crate::params::OptionsParams;
+use crate::population::Population;
+use crate::replacement::Replacement;
+use crate::selection::Draw;
+
+/// Generator liczb losowych przebiegu GA.
+///
+/// Jeden strumień na cały przebieg: populacja startowa i wszystkie kolejne
+/// pokolenia losowane są z tego samego generatora, więc przebieg o danym
+/// ziarnie da się odtworzyć bit w bit. To ten sam algorytm (ChaCha12) co
+/// `rand::rngs::StdRng`, ale z dostępem do pozycji w strumieniu, więc stan
+/// generatora można zapisać razem z przebiegiem (patrz [`crate::document`]).
+pub type GaRng = rand_chacha::ChaCha12Rng;
+
+/// Tworzy generator przebiegu z podanego ziarna.
+pub fn rng_from_seed(seed: u64) -> GaRng {
+    GaRng::seed_from_u64(seed)
+}
+
+/// Zapis przebiegu jednego pokolenia – materiał trybu nauki
+/// (patrz [`Engine::step_explained`]). Indeksy dotyczą populacji rodziców.
+#[derive(Clone, Debug, Default)]
+pub struct StepTrace {
+    /// Losowania selekcji w kolejności – kolejne pary to rodzice kolejnych kojarzeń.
+    pub draws:    Vec<Draw>,
+    /// Kojarzenia w kolejności tworzenia dzieci.
+    pub matings:  Vec<Mating>,
+    /// Wszystkie utworzone dzieci (z numerami) – także te, które nie
+    /// przeżyły zastępowania.
+    pub children: Vec<Chromosome>,
+    /// Liczba elit przeniesionych bez zmian.
+    pub elites:   usize,
+}
+
+/// Jedno kojarzenie: para rodziców i ich dzieci.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mating {
+    /// Indeksy rodziców w populacji.
+    pub parents:  (usize, usize),
+    /// Indeksy dzieci w [`StepTrace::children`] (jedno, gdy drugie odpadło).
+    pub children: Vec<usize>,
+}
+
+/// Silnik GA: funkcja celu optymalizowana przez algorytm.
+///
+/// Nie zna GUI ani wątków – [`Engine::step`] przyjmuje populację, parametry
+/// i generator liczb losowych, więc można go wołać z dowolnego miejsca.
+#[derive(Clone, Debug)]
+pub struct Engine {
+    pub objective: Objective,
+}
+
+impl Engine {
+    pub fn new(objective: Objective) -> Self {
+        Self { objective }
+    }
+
+    /// Tworzy losową populację startową o rozmiarze i dziedzinie z `params`.
+    pub fn random_population<R: Rng>(&self, params: &OptionsParams, rng: &mut R) -> Population {
+        Population::random(params, &self.objective, rng)
+    }
+
+    /// Oblicza jedno pokolenie: tworzy dzieci ([`Engine::breed`]) i wybiera
+    /// przeżywających według strategii zastępowania z `params.replacement`,
+    /// z zachowaniem `params.elitism` najlepszych rodziców.
+    /// Zastępuje `pop` nową, posortowaną populacją. Dzieci dostają kolejne
+    /// numery osobników, a przeżywający rodzice zachowują swoje.
+    pub fn step<R: Rng>(&self, pop: &mut Population, params: &OptionsParams, rng: &mut R) {
+        self.step_traced(pop, params, rng, None);
+    }
+
+    /// Jak [`Engine::step`], a przy tym zapisuje przebieg pokolenia:
+    /// losowania selekcji, kojarzenia, dzieci i liczbę elit. To ten sam kod
+    /// i te same losowania, więc wynik jest identyczny jak z `step`.
+    pub fn step_explained<R: Rng>(&self, pop: &mut Population, params: &OptionsParams, rng: &mut R) -> StepTrace {
+        let mut trace = StepTrace::default();
+        self.step_traced(pop, params, rng, Some(&mut trace));
+        trace
+    }
+
+    fn step_traced<R: Rng>(
+        &self,
+        pop: &mut Population,
+        params: &OptionsParams,
+        rng: &mut R,
+        mut trace: Option<&mut StepTrace>,
+    ) {
+        let mu = params.pop_size;
+        let parents = &pop.chromosomes;
+        let birth = pop.generation + 1;
+        let mut next_id = pop.next_id;
+        let mut breed = |count: usize, rng: &mut R| {
+            let mut children = self.breed_traced(parents, count, params, rng, trace.as_deref_mut());
+            for child in &mut children {
+                child.origin.id = next_id;
+                child.origin.birth = birth;
+                next_id += 1;
+            }
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.children.extend(children.iter().cloned());
+            }
+            children
+        };
+        // Populacja jest posortowana, więc elita to jej początek.
+        let elites = params.elitism.min(parents.len()).min(mu);
+        let replacement = &params.replacement;
+
+        let survivors = match replacement.strategy {
+            // -- Pokoleniowe: elita + dzieci do pełnego rozmiaru ------------
+            Replacement::Generational => {
+                let mut next = parents[..elites].to_vec();
+                next.extend(breed(mu - elites, rng));
+                next
+            }
+            // -- (μ+λ): rodzice i dzieci konkurują razem --------------------
+            Replacement::MuPlusLambda => {
+                let mut next = parents.clone();
+                next.extend(breed(replacement.lambda, rng));
+                best_of(next, mu, &params.fitness)
+            }
+            // -- (μ,λ): rodzice (poza elitą) odpadają, wybieramy z dzieci ---
+            Replacement::MuCommaLambda => {
+                let children = breed(replacement.lambda, rng);
+                let mut next = parents[..elites].to_vec();
+                next.extend(best_of(children, mu - elites, &params.fitness));
+                next
+            }
+            // -- Stacjonarne: kilkoro dzieci zastępuje najgorszych ----------
+            Replacement::SteadyState => {
+                let count = replacement.steady_count.min(mu - elites);
+                let mut next = parents.clone();
+                next.truncate(mu - count);
+                let missing = mu - next.len();
+                next.extend(breed(missing, rng));
+                next
+            }
+        };
+
+        if let Some(trace) = trace {
+            trace.elites = elites;
+        }
+        *pop = Population::from_chromosomes(survivors, birth, &params.fitness);
+        pop.next_id = next_id;
+    }
+
+    /// Tworzy `count` ocenionych dzieci: selekcja (metoda z `params.selection`),
+    /// krzyżowanie (każda para rodziców daje dwoje dzieci) i mutacja. Operatory
+    /// krzyżowania i mutacji zależą od reprezentacji (bitowej albo rzeczywistej).
+    /// W `Chromosome::origin` dzieci zapisani są rodzice i zastosowane
+    /// operatory; numer i pokolenie narodzin nadaje [`Engine::step`].
+    pub fn breed<R: Rng>(
+        &self,
+        parents: &[Chromosome],
+        count: usize,
+        params: &OptionsParams,
+        rng: &mut R,
+    ) -> Vec<Chromosome> {
+        self.breed_traced(parents, count, params, rng, None)
+    }
+
+    fn breed_traced<R: Rng>(
+        &self,
+        parents: &[Chromosome],
+        count: usize,
+        params: &OptionsParams,
+        rng: &mut R,
+        trace: Option<&mut StepTrace>,
+    ) -> Vec<Chromosome> {
+        let mut new_chromosomes: Vec<Chromosome> = Vec::with_capacity(count);
+        let mut matings = Vec::new();
+
+        // -- Selekcja: pula rodziców, para na każde dwoje dzieci -------------
+        let selector = params.selection.selector(params.tournament_k);
+        let draws = selector.draw(parents, count.div_ceil(2) * 2, rng);
+        let crossover = params.crossover.operator();
+
+        // -- Utwórz dzieci --------------------------------------------------
+        // Przy nieparzystej liczbie dzieci drugie dziecko ostatniej pary odpada.
+        for pair in draws.chunks_exact(2) {
+            let (parent_a, parent_b) = (&parents[pair[0].winner], &parents[pair[1].winner]);
+            let mut mating = Mating { parents: (pair[0].winner, pair[1].winner), children: Vec::new() };
+
+            // Punkty cięcia (None = bez krzyżowania) i dzieci z listą mutacji.
+            let (cuts, offspring) = match params.representation {
+                Representation::Binary => {
+                    let mask = rng
+                        .gen_bool(params.crossover_prob)
+                        .then(|| crossover.mask(parent_a.genes.len(), rng));
+                    let (mut genes_a, mut genes_b) = match &mask {
+                        Some(mask) => apply_mask(&parent_a.genes, &parent_b.genes, mask),
+                        None       => (parent_a.genes.clone(), parent_b.genes.clone()),
+                    };
+                    let mutated_a = mutate(&mut genes_a, params.mutation_prob, rng);
+                    let mutated_b = mutate(&mut genes_b, params.mutation_prob, rng);
+                    let cuts = mask.map(|mask| Origin::cut_points(&mask));
+                    let child = |genes| Chromosome::from_genes(genes, &params.vars, params.encoding);
+                    (cuts, [(child(genes_a), mutated_a), (child(genes_b), mutated_b)])
+                }
+                Representation::Real => {
+                    let crossed = rng.gen_bool(params.crossover_prob);
+                    let (mut xs_a, mut xs_b) = if crossed {
+                        params.real.crossover(&parent_a.xs, &parent_b.xs, rng)
+                    } else {
+                        (parent_a.xs.clone(), parent_b.xs.clone())
+                    };
+                    let mutated_a = params.real.mutate(&mut xs_a, &params.vars, params.mutation_prob, rng);
+                    let mutated_b = params.real.mutate(&mut xs_b, &params.vars, params.mutation_prob, rng);
+                    let child = |xs| Chromosome::from_xs(xs, &params.vars);
+                    (crossed.then(Vec::new), [(child(xs_a), mutated_a), (child(xs_b), mutated_b)])
+                }
+            };
+            // Pierwszym rodzicem dziecka jest ten, od którego bierze początek genomu.
+            let parent_ids = [
+                (parent_a.origin.id, parent_b.origin.id),
+                (parent_b.origin.id, parent_a.origin.id),
+            ];
+            let wanted = count - new_chromosomes.len();
+            for ((mut child, mutations), parents) in offspring.into_iter().zip(parent_ids).take(wanted) {
+                child.origin = Origin {
+                    parents: Some(parents),
+                    crossover: cuts.clone(),
+                    mutations,
+                    ..Origin::default()
+                };
+                child.evaluate(&self.objective);
+                mating.children.push(new_chromosomes.len());
+                new_chromosomes.push(child);
+            }
+            matings.push(mating);
+        }
+
+        if let Some(trace) = trace {
+            // Dzieci trafiają do zapisu po nadaniu numerów (w `step_traced`).
+            let base = trace.children.len();
+            for mating in &mut matings {
+                mating.children.iter_mut().for_each(|i| *i += base);
+            }
+            trace.matings.extend(matings);
+            trace.draws.extend(draws);
+        }
+        new_chromosomes
+    }
+}
+
+// `n` najlepszych chromosomów. Przekształcenia fitness są monotoniczne,
+// więc kolejność po fitness policzonym dla całej grupy jest poprawna.
+fn best_of(chromosomes: Vec<Chromosome>, n: usize, mode: &FitnessMode) -> Vec<Chromosome> {
+    let mut best = Population::from_chromosomes(chromosomes, 0, mode).chromosomes;
+    best.truncate(n);
+    best
+}
+
+// -- Mutacja bitowa ----------------------------------------------------------
+// Każdy bit może się losowo odwrócić z prawdopodobieństwem MUTATION_PROB.
+// Wyobraź sobie kosmiczne promieniowanie, które z rzadka przełącza
+// jeden bit w DNA. Zwraca indeksy odwróconych bitów.
+fn mutate<R: Rng>(genes: &mut [bool], prob: f64, rng: &mut R) -> Vec<usize> {
+    let mut flipped = Vec::new();
+    for (i, bit) in genes.iter_mut().enumerate() {
+        if rng.gen_bool(prob) {
+            *bit = !*bit;
+            flipped.push(i);
+        }
+    }
+    flipped
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngCore;
+
+    use super::*;
+    use crate::objective;
+    use crate::replacement::ReplacementParams;
+
+    fn run(params: &OptionsParams, generations: usize) -> Population {
+        let engine = Engine::new(objective::default_objective());
+        let mut rng = rng_from_seed(params.seed);
+        let mut pop = engine.random_population(params, &mut rng);
+        for _ in 0..generations {
+            engine.step(&mut pop, params, &mut rng);
+        }
+        pop
+    }
+
+    #[test]
+    fn step_keeps_size_order_and_counts_generations() {
+        let params = OptionsParams::default();
+        let pop = run(&params, 5);
+        assert_eq!(pop.generation, 5);
+        assert_eq!(pop.chromosomes.len(), params.pop_size);
+        assert!(pop.chromosomes.windows(2).all(|w| w[0].fitness >= w[1].fitness));
+    }
+
+    #[test]
+    fn same_seed_gives_same_run() {
+        let params = OptionsParams::default();
+        let (a, b) = (run(&params, 10), run(&params, 10));
+        let genes = |p: &Population| p.chromosomes.iter().map(|c| c.genes.clone()).collect::<Vec<_>>();
+        assert_eq!(genes(&a), genes(&b));
+    }
+
+    #[test]
+    fn step_explained_matches_step() {
+        let configs = [
+            OptionsParams { elitism: 2, ..OptionsParams::default() },
+            OptionsParams {
+                replacement: ReplacementParams { strategy: Replacement::MuPlusLambda, ..ReplacementParams::default() },
+                ..OptionsParams::default()
+            },
+            OptionsParams {
+                replacement: ReplacementParams { strategy: Replacement::SteadyState, ..ReplacementParams::default() },
+                ..OptionsParams::default()
+            },
+            OptionsParams { representation: Representation::Real, ..OptionsParams::default() },
+        ];
+        let engine = Engine::new(objective::default_objective());
+        for params in configs {
+            let mut rng = rng_from_seed(11);
+            let mut pop = engine.random_population(&params, &mut rng);
+            let (mut pop_b, mut rng_b) = (pop.clone(), rng.clone());
+            for _ in 0..5 {
+                engine.step(&mut pop, &params, &mut rng);
+                let trace = engine.step_explained(&mut pop_b, &params, &mut rng_b);
+
+                let ids = |p: &Population| p.chromosomes.iter().map(|c| c.origin.clone()).collect::<Vec<_>>();
+                assert_eq!(ids(&pop), ids(&pop_b));
+                assert_eq!(pop.next_id, pop_b.next_id);
+                assert!(pop.chromosomes.iter().zip(&pop_b.chromosomes).all(|(a, b)| a.genes == b.genes && a.xs == b.xs));
+
+                assert_eq!(trace.draws.len(), 2 * trace.matings.len());
+                assert!(trace.matings.iter().flat_map(|m| &m.children).all(|&i| i < trace.children.len()));
+                let born = pop_b.chromosomes.iter().filter(|c| c.origin.birth == pop_b.generation);
+                assert!(born.into_iter().all(|c| trace.children.iter().any(|t| t.origin.id == c.origin.id)));
+            }
+            assert_eq!(rng.next_u64(), rng_b.next_u64());
+        }
+    }
+}
@@ -0,0 +1,216 @@
+// ---------------------------------------------------------------------------
+// Eksport wyników do analizy (pandas, R, arkusz)
+//
+// Dwie tabele, każda jako CSV albo JSON (tablica rekordów):
+//   - populacja: ranga, numer osobnika, pokolenie narodzin, bity, zmienne,
+//     wartość f i fitness – wiersz na osobnika, od najlepszego,
+//   - statystyki: wiersz na pokolenie, kolumny jak w `GenerationStats`.
+// Kolumny obu formatów nazywają się tak samo. Wartości nieskończone i NaN
+// (np. niepoprawne osobniki, Hamming dla genomu rzeczywistego) zapisywane są
+// jako brak wartości: puste pole w CSV, `null` w JSON.
+// ---------------------------------------------------------------------------
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::population::Population;
+use crate::stats::GenerationStats;
+
+/// Format pliku eksportu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Csv, ExportFormat::Json];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Csv  => "CSV",
+            ExportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv  => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// Format wynikający z rozszerzenia pliku.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|f| f.extension() == ext)
+            .ok_or_else(|| format!("{}: nieznany format eksportu (csv | json)", path.display()))
+    }
+}
+
+/// Tabela populacji; `names` to nazwy zmiennych (kolumny x).
+pub fn population(pop: &Population, names: &[String], format: ExportFormat) -> String {
+    let mut columns: Vec<&str> = vec!["rank", "id", "birth", "bits"];
+    columns.extend(names.iter().map(String::as_str));
+    columns.extend(["value", "fitness"]);
+
+    let rows: Vec<Vec<Value>> = pop.chromosomes
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let bits: String = c.genes.iter().map(|&b| if b { '1' } else { '0' }).collect();
+            let mut row = vec![Value::from(i + 1), Value::from(c.origin.id), Value::from(c.origin.birth), Value::from(bits)];
+            row.extend(c.xs.iter().map(|&x| number(x)));
+            row.extend([number(c.value), number(c.fitness)]);
+            row
+        })
+        .collect();
+    table(&columns, rows, format)
+}
+
+/// Tabela statystyk kolejnych pokoleń.
+pub fn stats(history: &[GenerationStats], format: ExportFormat) -> String {
+    let columns = ["generation", "best", "mean", "median", "worst", "std_dev", "hamming", "spread", "unique"];
+    let rows = history
+        .iter()
+        .map(|s| {
+            vec![
+                Value::from(s.generation),
+                number(s.best),
+                number(s.mean),
+                number(s.median),
+                number(s.worst),
+                number(s.std_dev),
+                number(s.hamming),
+                number(s.spread),
+                Value::from(s.unique),
+            ]
+        })
+        .collect();
+    table(&columns, rows, format)
+}
+
+/// Zapisuje wyeksportowaną tabelę do pliku.
+pub fn save(path: &Path, text: &str) -> Result<(), String> {
+    std::fs::write(path, text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+// Liczba JSON albo `null` dla wartości nieskończonych i NaN.
+fn number(v: f64) -> Value {
+    serde_json::Number::from_f64(v).map_or(Value::Null, Value::Number)
+}
+
+fn table(columns: &[&str], rows: Vec<Vec<Value>>, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => {
+            // Pola to liczby, bity i nazwy zmiennych – nie wymagają cudzysłowów.
+            let mut out = columns.join(",");
+            out.push('\n');
+            for row in rows {
+                let fields: Vec<String> = row
+                    .iter()
+                    .map(|v| match v {
+                        Value::Null      => String::new(),
+                        Value::String(s) => s.clone(),
+                        v                => v.to_string(),
+                    })
+                    .collect();
+                let _ = writeln!(out, "{}", fields.join(","));
+            }
+            out
+        }
+        ExportFormat::Json => {
+            let records: Vec<Value> = rows
+                .into_iter()
+                .map(|row| {
+                    let record: Map<String, Value> = columns.iter().map(|c| c.to_string()).zip(row).collect();
+                    Value::Object(record)
+                })
+                .collect();
+            let mut out = serde_json::to_string_pretty(&records).expect("wartości JSON zawsze dają się zapisać");
+            out.push('\n');
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromosome::{Chromosome, VarSpec};
+    use crate::encoding::Encoding;
+    use crate::fitness::FitnessMode;
+
+    /// Populacja trzech osobników o dwóch zmiennych; trzeci ma f = NaN.
+    fn pop() -> Population {
+        let vars = [VarSpec { min: 0.0, max: 3.0, bits: 2 }, VarSpec { min: 0.0, max: 3.0, bits: 2 }];
+        let chromosomes = ["0110", "1111", "0000"]
+            .iter()
+            .zip([3.0, 6.0, f64::NAN])
+            .enumerate()
+            .map(|(id, (genes, value))| {
+                let genes = genes.chars().map(|b| b == '1').collect();
+                let mut c = Chromosome { value, ..Chromosome::from_genes(genes, &vars, Encoding::Binary) };
+                c.origin.id = id as u64;
+                c
+            })
+            .collect();
+        Population::from_chromosomes(chromosomes, 2, &FitnessMode::default())
+    }
+
+    fn names() -> Vec<String> {
+        vec!["x1".into(), "x2".into()]
+    }
+
+    #[test]
+    fn population_csv_has_header_and_row_per_individual() {
+        let csv = population(&pop(), &names(), ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "rank,id,birth,bits,x1,x2,value,fitness");
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "1,1,0,1111,3.0,3.0,6.0,6.0");
+        // NaN jako puste pole, fitness -inf również.
+        assert_eq!(lines[3], "3,2,0,0000,0.0,0.0,,");
+    }
+
+    #[test]
+    fn stats_csv_has_header_and_row_per_generation() {
+        let vars = vec![VarSpec { min: 0.0, max: 3.0, bits: 2 }; 2];
+        let history = vec![GenerationStats::of(&pop(), &vars); 5];
+        let csv = stats(&history, ExportFormat::Csv);
+        assert_eq!(csv.lines().next(), Some("generation,best,mean,median,worst,std_dev,hamming,spread,unique"));
+        assert_eq!(csv.lines().count(), 6);
+    }
+
+    #[test]
+    fn json_round_trip_keeps_columns_and_nulls_non_finite_values() {
+        let json = population(&pop(), &names(), ExportFormat::Json);
+        let records: Vec<Map<String, Value>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(records.len(), 3);
+        let columns: Vec<&str> = records[0].keys().map(String::as_str).collect();
+        assert_eq!(columns, ["rank", "id", "birth", "bits", "x1", "x2", "value", "fitness"]);
+        assert_eq!(records[0]["value"], 6.0);
+        assert_eq!(records[0]["bits"], "1111");
+        assert_eq!(records[2]["value"], Value::Null);
+        assert_eq!(records[2]["fitness"], Value::Null);
+
+        let real = [VarSpec::default()];
+        let single = Population::from_chromosomes(vec![Chromosome::from_xs(vec![1.0], &real)], 0, &FitnessMode::default());
+        let history = [GenerationStats::of(&single, &real)];
+        let records: Vec<Map<String, Value>> = serde_json::from_str(&stats(&history, ExportFormat::Json)).unwrap();
+        assert_eq!(records[0]["hamming"], Value::Null);
+        assert_eq!(records[0]["unique"], 1);
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(ExportFormat::from_path(Path::new("a/b.CSV")), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_path(Path::new("wyniki.json")), Ok(ExportFormat::Json));
+        assert!(ExportFormat::from_path(Path::new("wyniki.xlsx")).is_err());
+        assert!(ExportFormat::from_path(Path::new("wyniki")).is_err());
+    }
+}
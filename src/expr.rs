@@ -0,0 +1,645 @@
+// ---------------------------------------------------------------------------
+// Parser wyrażeń matematycznych dla funkcji celu
+//
+// Tekst wpisany przez użytkownika (np. "sin(x)*x^2") jest kolejno:
+//   1. dzielony na tokeny (z zapamiętaną pozycją każdego tokenu),
+//   2. parsowany metodą zejść rekurencyjnych do drzewa składniowego,
+//   3. upraszczany (zwijanie stałych) i kompilowany do kodu postfiksowego,
+//      który wykonuje się na małym stosie bez alokacji.
+//
+// Gramatyka (od najniższego priorytetu):
+//   expr    := term (('+' | '-') term)*
+//   term    := unary (('*' | '/') unary)*
+//   unary   := ('+' | '-') unary | power
+//   power   := primary ('^' unary)?          // prawostronnie łączny: 2^3^2 = 2^9
+//   primary := liczba | stała | zmienna | funkcja '(' expr (',' expr)* ')' | '(' expr ')'
+// ---------------------------------------------------------------------------
+
+use std::fmt;
+
+/// Maksymalna głębokość stosu ewaluatora.
+const MAX_STACK: usize = 64;
+
+/// Maksymalne zagnieżdżenie nawiasów, znaków i potęg w parserze – chroni
+/// przed przepełnieniem stosu przy wejściu typu "((((…x))))" albo "------x".
+const MAX_DEPTH: usize = 64;
+
+/// Maksymalna liczba zmiennych x1..xN rozpoznawana przez [`Expr::parse_auto`].
+pub const MAX_VARS: usize = 32;
+
+/// Błąd parsowania wraz z pozycją (indeks znaku od 0) w tekście źródłowym.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub pos: usize,
+    pub msg: String,
+}
+
+impl ParseError {
+    fn new(pos: usize, msg: impl Into<String>) -> Self {
+        Self { pos, msg: msg.into() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pozycja {}: {}", self.pos + 1, self.msg)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// -- Funkcje i stałe ---------------------------------------------------------
+
+type Fn1 = fn(f64) -> f64;
+type Fn2 = fn(f64, f64) -> f64;
+
+fn sign(x: f64) -> f64 {
+    if x > 0.0 { 1.0 } else if x < 0.0 { -1.0 } else { 0.0 }
+}
+
+const FUNCS1: &[(&str, Fn1)] = &[
+    ("sin",   f64::sin),
+    ("cos",   f64::cos),
+    ("tan",   f64::tan),
+    ("asin",  f64::asin),
+    ("acos",  f64::acos),
+    ("atan",  f64::atan),
+    ("sinh",  f64::sinh),
+    ("cosh",  f64::cosh),
+    ("tanh",  f64::tanh),
+    ("exp",   f64::exp),
+    ("ln",    f64::ln),
+    ("log",   f64::log10),
+    ("log10", f64::log10),
+    ("log2",  f64::log2),
+    ("sqrt",  f64::sqrt),
+    ("abs",   f64::abs),
+    ("floor", f64::floor),
+    ("ceil",  f64::ceil),
+    ("round", f64::round),
+    ("sign",  sign),
+];
+
+const FUNCS2: &[(&str, Fn2)] = &[
+    ("min",   f64::min),
+    ("max",   f64::max),
+    ("pow",   f64::powf),
+    ("atan2", f64::atan2),
+];
+
+const CONSTS: &[(&str, f64)] = &[
+    ("pi",  std::f64::consts::PI),
+    ("e",   std::f64::consts::E),
+    ("tau", std::f64::consts::TAU),
+];
+
+// -- Tokenizer ---------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Num(f64),
+    Ident(String),
+    Sym(char),
+    End,
+}
+
+fn tokenize(src: &str) -> Result<Vec<(Tok, usize)>, ParseError> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // Wykładnik: 1e-3, 2.5E+4 – ale samo "2e" to liczba 2 i stała e.
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| ParseError::new(start, format!("niepoprawna liczba \"{text}\"")))?;
+            out.push((Tok::Num(value), start));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            out.push((Tok::Ident(chars[start..i].iter().collect()), start));
+        } else if "+-*/^(),".contains(c) {
+            out.push((Tok::Sym(c), i));
+            i += 1;
+        } else {
+            return Err(ParseError::new(i, format!("nieoczekiwany znak '{c}'")));
+        }
+    }
+    out.push((Tok::End, chars.len()));
+    Ok(out)
+}
+
+// -- Drzewo składniowe i parser ----------------------------------------------
+
+#[derive(Clone, Debug)]
+enum Node {
+    Num(f64),
+    Var(usize),
+    Neg(Box<Node>),
+    Bin(char, Box<Node>, Box<Node>),
+    Call1(Fn1, Box<Node>),
+    Call2(Fn2, Box<Node>, Box<Node>),
+}
+
+struct Parser<'a> {
+    toks: Vec<(Tok, usize)>,
+    idx:   usize,
+    vars:  &'a [&'a str],
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Tok {
+        &self.toks[self.idx].0
+    }
+
+    fn pos(&self) -> usize {
+        self.toks[self.idx].1
+    }
+
+    fn next(&mut self) -> (Tok, usize) {
+        let t = self.toks[self.idx].clone();
+        if self.idx + 1 < self.toks.len() {
+            self.idx += 1;
+        }
+        t
+    }
+
+    fn eat(&mut self, sym: char) -> bool {
+        if *self.peek() == Tok::Sym(sym) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, sym: char) -> Result<(), ParseError> {
+        if self.eat(sym) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("oczekiwano '{sym}'")))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let found = match self.peek() {
+            Tok::End      => "koniec wyrażenia".to_string(),
+            Tok::Num(v)   => format!("liczbę {v}"),
+            Tok::Ident(s) => format!("\"{s}\""),
+            Tok::Sym(c)   => format!("'{c}'"),
+        };
+        ParseError::new(self.pos(), format!("{expected}, napotkano {found}"))
+    }
+
+    fn expr(&mut self) -> Result<Node, ParseError> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Tok::Sym(c @ ('+' | '-')) => *c,
+                _ => return Ok(lhs),
+            };
+            self.next();
+            let rhs = self.term()?;
+            lhs = Node::Bin(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn term(&mut self) -> Result<Node, ParseError> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Tok::Sym(c @ ('*' | '/')) => *c,
+                _ => return Ok(lhs),
+            };
+            self.next();
+            let rhs = self.unary()?;
+            lhs = Node::Bin(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    /// Każde zagnieżdżenie w gramatyce przechodzi przez `unary`, więc tu
+    /// liczona jest głębokość rekurencji.
+    fn unary(&mut self) -> Result<Node, ParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(ParseError::new(self.pos(), "wyrażenie jest zbyt głęboko zagnieżdżone"));
+        }
+        self.depth += 1;
+        let node = self.signed();
+        self.depth -= 1;
+        node
+    }
+
+    fn signed(&mut self) -> Result<Node, ParseError> {
+        if self.eat('-') {
+            return Ok(Node::Neg(Box::new(self.unary()?)));
+        }
+        if self.eat('+') {
+            return self.unary();
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Node, ParseError> {
+        let base = self.primary()?;
+        if self.eat('^') {
+            let exp = self.unary()?;
+            return Ok(Node::Bin('^', Box::new(base), Box::new(exp)));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Node, ParseError> {
+        let (tok, pos) = match self.peek() {
+            Tok::Num(_) | Tok::Ident(_) => self.next(),
+            Tok::Sym('(') => {
+                self.next();
+                let inner = self.expr()?;
+                self.expect(')')?;
+                return Ok(inner);
+            }
+            _ => return Err(self.unexpected("oczekiwano liczby, zmiennej lub '('")),
+        };
+
+        let name = match tok {
+            Tok::Num(v) => return Ok(Node::Num(v)),
+            Tok::Ident(name) => name,
+            _ => unreachable!(),
+        };
+
+        if *self.peek() == Tok::Sym('(') {
+            return self.call(&name, pos);
+        }
+        if let Some(i) = self.vars.iter().position(|v| *v == name) {
+            return Ok(Node::Var(i));
+        }
+        if let Some(&(_, v)) = CONSTS.iter().find(|(n, _)| *n == name) {
+            return Ok(Node::Num(v));
+        }
+        if FUNCS1.iter().any(|(n, _)| *n == name) || FUNCS2.iter().any(|(n, _)| *n == name) {
+            return Err(ParseError::new(pos, format!("funkcja \"{name}\" wymaga nawiasów")));
+        }
+        Err(ParseError::new(pos, format!("nieznana zmienna lub stała \"{name}\"")))
+    }
+
+    fn call(&mut self, name: &str, pos: usize) -> Result<Node, ParseError> {
+        self.expect('(')?;
+        let mut args = vec![self.expr()?];
+        while self.eat(',') {
+            args.push(self.expr()?);
+        }
+        self.expect(')')?;
+
+        let arity_err = |n: usize| {
+            ParseError::new(pos, format!("funkcja \"{name}\" przyjmuje {n} arg., podano {}", args.len()))
+        };
+        if let Some(&(_, f)) = FUNCS1.iter().find(|(n, _)| *n == name) {
+            if args.len() != 1 {
+                return Err(arity_err(1));
+            }
+            let a = args.pop().unwrap();
+            return Ok(Node::Call1(f, Box::new(a)));
+        }
+        if let Some(&(_, f)) = FUNCS2.iter().find(|(n, _)| *n == name) {
+            if args.len() != 2 {
+                return Err(arity_err(2));
+            }
+            let b = args.pop().unwrap();
+            let a = args.pop().unwrap();
+            return Ok(Node::Call2(f, Box::new(a), Box::new(b)));
+        }
+        Err(ParseError::new(pos, format!("nieznana funkcja \"{name}\"")))
+    }
+}
+
+fn apply_bin(op: char, a: f64, b: f64) -> f64 {
+    match op {
+        '+' => a + b,
+        '-' => a - b,
+        '*' => a * b,
+        '/' => a / b,
+        '^' => a.powf(b),
+        _ => unreachable!(),
+    }
+}
+
+/// Zwija poddrzewa złożone wyłącznie ze stałych do pojedynczej liczby.
+fn fold(node: Node) -> Node {
+    match node {
+        Node::Neg(a) => match fold(*a) {
+            Node::Num(v) => Node::Num(-v),
+            a => Node::Neg(Box::new(a)),
+        },
+        Node::Bin(op, a, b) => match (fold(*a), fold(*b)) {
+            (Node::Num(x), Node::Num(y)) => Node::Num(apply_bin(op, x, y)),
+            (a, b) => Node::Bin(op, Box::new(a), Box::new(b)),
+        },
+        Node::Call1(f, a) => match fold(*a) {
+            Node::Num(v) => Node::Num(f(v)),
+            a => Node::Call1(f, Box::new(a)),
+        },
+        Node::Call2(f, a, b) => match (fold(*a), fold(*b)) {
+            (Node::Num(x), Node::Num(y)) => Node::Num(f(x, y)),
+            (a, b) => Node::Call2(f, Box::new(a), Box::new(b)),
+        },
+        leaf => leaf,
+    }
+}
+
+// -- Kod postfiksowy ---------------------------------------------------------
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Const(f64),
+    Var(usize),
+    Neg,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Call1(Fn1),
+    Call2(Fn2),
+}
+
+/// Emituje kod dla `node`; zwraca maksymalną głębokość stosu poddrzewa.
+fn emit(node: &Node, ops: &mut Vec<Op>) -> usize {
+    match node {
+        Node::Num(v) => {
+            ops.push(Op::Const(*v));
+            1
+        }
+        Node::Var(i) => {
+            ops.push(Op::Var(*i));
+            1
+        }
+        Node::Neg(a) => {
+            let d = emit(a, ops);
+            ops.push(Op::Neg);
+            d
+        }
+        Node::Call1(f, a) => {
+            let d = emit(a, ops);
+            ops.push(Op::Call1(*f));
+            d
+        }
+        Node::Bin(op, a, b) => {
+            let da = emit(a, ops);
+            let db = emit(b, ops);
+            ops.push(match op {
+                '+' => Op::Add,
+                '-' => Op::Sub,
+                '*' => Op::Mul,
+                '/' => Op::Div,
+                _   => Op::Pow,
+            });
+            da.max(db + 1)
+        }
+        Node::Call2(f, a, b) => {
+            let da = emit(a, ops);
+            let db = emit(b, ops);
+            ops.push(Op::Call2(*f));
+            da.max(db + 1)
+        }
+    }
+}
+
+/// Ustala listę zmiennych wyrażenia na podstawie użytych nazw:
+///   - `x`, `y`, `z`      → 1–3 zmienne (x zawsze pierwsza, y druga, z trzecia),
+///   - `x1`, `x2`, … `xN` → N zmiennych (N = największy użyty indeks).
+///
+/// Pozostałe identyfikatory (funkcje, stałe, literówki) rozstrzyga parser.
+fn detect_vars(toks: &[(Tok, usize)]) -> Result<Vec<String>, ParseError> {
+    const XYZ: [&str; 3] = ["x", "y", "z"];
+    let mut xyz_dims = 0;
+    let mut xyz_pos = None;
+    let mut idx_dims = 0;
+    let mut idx_pos = None;
+
+    for (i, (tok, pos)) in toks.iter().enumerate() {
+        let Tok::Ident(name) = tok else { continue };
+        if matches!(toks.get(i + 1), Some((Tok::Sym('('), _))) {
+            continue;
+        }
+        if let Some(k) = XYZ.iter().position(|v| v == name) {
+            xyz_dims = xyz_dims.max(k + 1);
+            xyz_pos.get_or_insert(*pos);
+        } else if let Some(n) = name.strip_prefix('x').and_then(|d| d.parse::<usize>().ok()) {
+            if n == 0 || n > MAX_VARS {
+                return Err(ParseError::new(*pos, format!("dozwolone zmienne to x1..x{MAX_VARS}")));
+            }
+            idx_dims = idx_dims.max(n);
+            idx_pos.get_or_insert(*pos);
+        }
+    }
+
+    match (xyz_pos, idx_pos) {
+        (Some(a), Some(b)) => Err(ParseError::new(
+            a.max(b),
+            "nie można mieszać zmiennych x, y, z ze zmiennymi x1, x2, …",
+        )),
+        (None, Some(_)) => Ok((1..=idx_dims).map(|n| format!("x{n}")).collect()),
+        _ => Ok(XYZ[..xyz_dims.max(1)].iter().map(|v| v.to_string()).collect()),
+    }
+}
+
+/// Skompilowane wyrażenie gotowe do szybkiej ewaluacji.
+#[derive(Clone, Debug)]
+pub struct Expr {
+    source: String,
+    vars:   Vec<String>,
+    ops:    Vec<Op>,
+}
+
+impl Expr {
+    /// Parsuje wyrażenie jednej zmiennej `x`.
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        Self::parse_with_vars(src, &["x"])
+    }
+
+    /// Parsuje wyrażenie dowolnej liczby zmiennych, ustalając je z użytych
+    /// nazw: `x`, `y`, `z` albo `x1`, `x2`, …, `xN`.
+    pub fn parse_auto(src: &str) -> Result<Self, ParseError> {
+        let vars = detect_vars(&tokenize(src)?)?;
+        let names: Vec<&str> = vars.iter().map(String::as_str).collect();
+        Self::parse_with_vars(src, &names)
+    }
+
+    /// Parsuje wyrażenie zmiennych o podanych nazwach; w [`Expr::eval_at`]
+    /// wartości przekazuje się w tej samej kolejności.
+    pub fn parse_with_vars(src: &str, vars: &[&str]) -> Result<Self, ParseError> {
+        let mut parser = Parser { toks: tokenize(src)?, idx: 0, vars, depth: 0 };
+        if *parser.peek() == Tok::End {
+            return Err(ParseError::new(0, "puste wyrażenie"));
+        }
+        let tree = parser.expr()?;
+        if *parser.peek() != Tok::End {
+            return Err(parser.unexpected("oczekiwano operatora"));
+        }
+
+        let mut ops = Vec::new();
+        if emit(&fold(tree), &mut ops) > MAX_STACK {
+            return Err(ParseError::new(0, "wyrażenie jest zbyt złożone"));
+        }
+        let vars = vars.iter().map(|v| v.to_string()).collect();
+        Ok(Self { source: src.to_string(), vars, ops })
+    }
+
+    /// Tekst źródłowy, z którego powstało wyrażenie.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Nazwy zmiennych w kolejności oczekiwanej przez [`Expr::eval_at`].
+    pub fn vars(&self) -> &[String] {
+        &self.vars
+    }
+
+    /// Wartość wyrażenia jednej zmiennej w punkcie `x`.
+    pub fn eval(&self, x: f64) -> f64 {
+        self.eval_at(&[x])
+    }
+
+    /// Wartość wyrażenia dla podanych wartości zmiennych.
+    pub fn eval_at(&self, vars: &[f64]) -> f64 {
+        let mut stack = [0.0_f64; MAX_STACK];
+        let mut sp = 0;
+        for op in &self.ops {
+            match *op {
+                Op::Const(v) => { stack[sp] = v; sp += 1; }
+                Op::Var(i)   => { stack[sp] = vars[i]; sp += 1; }
+                Op::Neg      => stack[sp - 1] = -stack[sp - 1],
+                Op::Call1(f) => stack[sp - 1] = f(stack[sp - 1]),
+                Op::Call2(f) => { sp -= 1; stack[sp - 1] = f(stack[sp - 1], stack[sp]); }
+                Op::Add      => { sp -= 1; stack[sp - 1] += stack[sp]; }
+                Op::Sub      => { sp -= 1; stack[sp - 1] -= stack[sp]; }
+                Op::Mul      => { sp -= 1; stack[sp - 1] *= stack[sp]; }
+                Op::Div      => { sp -= 1; stack[sp - 1] /= stack[sp]; }
+                Op::Pow      => { sp -= 1; stack[sp - 1] = stack[sp - 1].powf(stack[sp]); }
+            }
+        }
+        stack[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(src: &str, x: f64) -> f64 {
+        Expr::parse(src).unwrap().eval(x)
+    }
+
+    fn err(src: &str) -> ParseError {
+        Expr::parse_auto(src).unwrap_err()
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(eval("-x^2", 3.0), -9.0);
+        assert_eq!(eval("2^3^2", 0.0), 512.0);
+        assert_eq!(eval("2*-3", 0.0), -6.0);
+        assert_eq!(eval("1+2*x", 4.0), 9.0);
+        assert_eq!(eval("8/4/2", 0.0), 1.0);
+        assert_eq!(eval("10-4-3", 0.0), 3.0);
+        assert_eq!(eval("(1+2)*x", 2.0), 6.0);
+        assert_eq!(eval("2e-1 + 2*e", 0.0), 0.2 + 2.0 * std::f64::consts::E);
+        assert_eq!(err("2e").pos, 1);
+    }
+
+    #[test]
+    fn constants_are_folded() {
+        let e = Expr::parse("2*pi + sqrt(16) - max(1, 3)").unwrap();
+        assert_eq!(e.ops.len(), 1);
+        assert_eq!(e.eval(0.0), 2.0 * std::f64::consts::PI + 1.0);
+
+        let e = Expr::parse("x * (2 + 3)").unwrap();
+        assert_eq!(e.ops.len(), 3);
+        assert_eq!(e.eval(2.0), 10.0);
+    }
+
+    #[test]
+    fn arity_and_unknown_names_report_position() {
+        let e = err("1 + sin(x, 2)");
+        assert_eq!(e.pos, 4);
+        assert!(e.msg.contains("przyjmuje 1"), "{}", e.msg);
+
+        let e = err("max(x)");
+        assert_eq!(e.pos, 0);
+        assert!(e.msg.contains("przyjmuje 2"), "{}", e.msg);
+
+        let e = err("x + foo");
+        assert_eq!(e.pos, 4);
+        assert!(e.msg.contains("\"foo\""), "{}", e.msg);
+
+        assert_eq!(err("x * bar(x)").pos, 4);
+        assert_eq!(err("sin + x").pos, 0);
+        assert_eq!(err("x +").pos, 3);
+        assert_eq!(err("x # 2").pos, 2);
+        assert_eq!(err("").pos, 0);
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected() {
+        let depth = MAX_DEPTH * 4;
+        let parens = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(err(&parens).msg.contains("zagnieżdżone"));
+        assert!(err(&format!("{}x", "-".repeat(depth))).msg.contains("zagnieżdżone"));
+        assert!(err(&format!("{}x", "2^".repeat(depth))).msg.contains("zagnieżdżone"));
+        assert!(err(&format!("{}x{}", "sin(".repeat(depth), ")".repeat(depth))).msg.contains("zagnieżdżone"));
+
+        let shallow = format!("{}x{}", "(".repeat(16), ")".repeat(16));
+        assert_eq!(Expr::parse(&shallow).unwrap().eval(2.0), 2.0);
+    }
+
+    #[test]
+    fn parse_auto_detects_and_orders_variables() {
+        assert_eq!(Expr::parse_auto("2").unwrap().vars(), ["x"]);
+        assert_eq!(Expr::parse_auto("sin(y)").unwrap().vars(), ["x", "y"]);
+        assert_eq!(Expr::parse_auto("z + x").unwrap().vars(), ["x", "y", "z"]);
+        assert_eq!(Expr::parse_auto("x3 - x1").unwrap().vars(), ["x1", "x2", "x3"]);
+        assert_eq!(Expr::parse_auto("exp(x) + e").unwrap().vars(), ["x"]);
+
+        assert!(err("x + x1").msg.contains("mieszać"));
+        assert_eq!(err("x0").pos, 0);
+        assert!(Expr::parse_auto(&format!("x{}", MAX_VARS + 1)).is_err());
+    }
+
+    #[test]
+    fn eval_at_matches_hand_computed_values() {
+        let e = Expr::parse_auto("x1^2 + 3*x2 - x3/2").unwrap();
+        assert_eq!(e.eval_at(&[2.0, 1.0, 4.0]), 5.0);
+
+        let e = Expr::parse_auto("atan2(y, x) + abs(x - y)").unwrap();
+        assert_eq!(e.eval_at(&[1.0, 1.0]), std::f64::consts::FRAC_PI_4);
+
+        let e = Expr::parse_with_vars("a*b + min(a, b)", &["a", "b"]).unwrap();
+        assert_eq!(e.eval_at(&[3.0, -2.0]), -8.0);
+
+        assert_eq!(eval("sign(x) * floor(x) + ceil(0.5)", -2.5), 4.0);
+        assert!(eval("ln(x)", -1.0).is_nan());
+        assert_eq!(eval("1/x", 0.0), f64::INFINITY);
+    }
+}
@@ -0,0 +1,183 @@
+// ---------------------------------------------------------------------------
+// Kierunek optymalizacji i przekształcenia fitness
+//
+// Chromosom przechowuje osobno:
+//   - `value`   – wartość funkcji celu f(x), pokazywaną na wykresie,
+//   - `fitness` – wartość używaną przez selekcję (zawsze "im więcej, tym lepiej").
+//
+// Fitness powstaje w dwóch krokach:
+//   1. kierunek:       s = f(x) przy maksymalizacji, s = -f(x) przy minimalizacji
+//                      (minimalizacja to po prostu negacja funkcji celu),
+//   2. przekształcenie: s bez zmian, przesunięcie do wartości dodatnich
+//                      albo skalowanie wykładnicze.
+//
+// Przekształcenia są monotoniczne, więc nie zmieniają kolejności osobników –
+// zmieniają tylko proporcje między nimi (co ma znaczenie np. dla selekcji
+// proporcjonalnej do fitness).
+// ---------------------------------------------------------------------------
+
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::Chromosome;
+
+/// Kierunek optymalizacji funkcji celu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Maximize,
+    Minimize,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 2] = [Direction::Maximize, Direction::Minimize];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Direction::Maximize => "Maksymalizacja",
+            Direction::Minimize => "Minimalizacja",
+        }
+    }
+}
+
+/// Przekształcenie zorientowanej wartości `s` na fitness selekcji.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FitnessTransform {
+    /// fitness = s
+    Identity,
+    /// fitness = s − s_min + ε (najgorszy osobnik ma fitness bliski zera, ale dodatni)
+    Offset,
+    /// fitness = exp(c · (s − s_max)) (najlepszy ma fitness 1, reszta w (0, 1))
+    Exponential,
+}
+
+impl FitnessTransform {
+    pub const ALL: [FitnessTransform; 3] = [
+        FitnessTransform::Identity,
+        FitnessTransform::Offset,
+        FitnessTransform::Exponential,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FitnessTransform::Identity    => "Brak",
+            FitnessTransform::Offset      => "Przesunięcie do dodatnich",
+            FitnessTransform::Exponential => "Skalowanie wykładnicze",
+        }
+    }
+}
+
+/// Kompletny sposób liczenia fitness z wartości funkcji celu.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FitnessMode {
+    pub direction: Direction,
+    pub transform: FitnessTransform,
+    /// Współczynnik c skalowania wykładniczego.
+    pub exp_scale: f64,
+}
+
+impl Default for FitnessMode {
+    fn default() -> Self {
+        Self {
+            direction: Direction::Maximize,
+            transform: FitnessTransform::Identity,
+            exp_scale: 1.0,
+        }
+    }
+}
+
+impl FitnessMode {
+    /// Zorientowana wartość: większa = lepsza. NaN (np. ln z liczby ujemnej)
+    /// traktujemy jak najgorszy możliwy wynik w obu kierunkach.
+    pub fn oriented(&self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NEG_INFINITY;
+        }
+        match self.direction {
+            Direction::Maximize => value,
+            Direction::Minimize => -value,
+        }
+    }
+
+    /// Przypisuje `fitness` wszystkim chromosomom na podstawie ich `value`.
+    ///
+    /// Przesunięcie i skalowanie wykładnicze zależą od całej populacji
+    /// (od s_min / s_max), dlatego liczone są dla wszystkich naraz.
+    pub fn assign(&self, chromosomes: &mut [Chromosome]) {
+        let oriented: Vec<f64> = chromosomes.iter().map(|c| self.oriented(c.value)).collect();
+
+        // Skrajne wartości liczymy tylko po skończonych s – inaczej jeden
+        // osobnik z -inf zepsułby fitness całej populacji.
+        let finite = oriented.iter().copied().filter(|s| s.is_finite());
+        let s_min = finite.clone().fold(f64::INFINITY, f64::min);
+        let s_max = finite.fold(f64::NEG_INFINITY, f64::max);
+        let (s_min, s_max) = if s_min <= s_max { (s_min, s_max) } else { (0.0, 0.0) };
+
+        for (c, s) in chromosomes.iter_mut().zip(oriented) {
+            c.fitness = match self.transform {
+                FitnessTransform::Identity => s,
+                FitnessTransform::Offset => {
+                    let eps = 1e-6 * (s_max - s_min).max(1.0);
+                    (s - s_min + eps).max(0.0)
+                }
+                FitnessTransform::Exponential => (self.exp_scale * (s - s_max)).exp(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromosome::VarSpec;
+
+    fn scored(mode: FitnessMode, values: &[f64]) -> Vec<f64> {
+        let vars = [VarSpec::default()];
+        let mut chromosomes: Vec<Chromosome> = values
+            .iter()
+            .map(|&value| Chromosome { value, ..Chromosome::from_xs(vec![0.0], &vars) })
+            .collect();
+        mode.assign(&mut chromosomes);
+        chromosomes.iter().map(|c| c.fitness).collect()
+    }
+
+    fn mode(direction: Direction, transform: FitnessTransform) -> FitnessMode {
+        FitnessMode { direction, transform, exp_scale: 1.0 }
+    }
+
+    #[test]
+    fn direction_orients_values_and_nan_is_worst() {
+        let max = mode(Direction::Maximize, FitnessTransform::Identity);
+        let min = mode(Direction::Minimize, FitnessTransform::Identity);
+        assert_eq!(scored(max, &[1.0, -2.0]), [1.0, -2.0]);
+        assert_eq!(scored(min, &[1.0, -2.0]), [-1.0, 2.0]);
+        assert_eq!(max.oriented(f64::NAN), f64::NEG_INFINITY);
+        assert_eq!(min.oriented(f64::NAN), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn offset_makes_fitness_positive_and_keeps_order() {
+        let offset = mode(Direction::Minimize, FitnessTransform::Offset);
+        let fitness = scored(offset, &[3.0, -1.0, 5.0, f64::NAN]);
+        assert!(fitness[..3].iter().all(|&f| f > 0.0));
+        assert!(fitness[1] > fitness[0] && fitness[0] > fitness[2]);
+        assert!(fitness[2] < 1e-3);
+        assert_eq!(fitness[3], 0.0);
+    }
+
+    #[test]
+    fn exponential_gives_best_one_and_rest_below() {
+        let exp = FitnessMode { exp_scale: 2.0, ..mode(Direction::Maximize, FitnessTransform::Exponential) };
+        let fitness = scored(exp, &[0.0, 1.0, f64::NEG_INFINITY]);
+        assert_eq!(fitness[1], 1.0);
+        assert!((fitness[0] - (-2.0_f64).exp()).abs() < 1e-12);
+        assert_eq!(fitness[2], 0.0);
+    }
+
+    #[test]
+    fn all_infinite_values_do_not_produce_nan() {
+        for transform in FitnessTransform::ALL {
+            let fitness = scored(mode(Direction::Maximize, transform), &[f64::NEG_INFINITY, f64::NAN]);
+            assert!(fitness.iter().all(|f| !f.is_nan()), "{transform:?}: {fitness:?}");
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// ---------------------------------------------------------------------------
+// Genealogia
+//
+// Każdy chromosom nosi swoje pochodzenie (`Origin`): numer osobnika,
+// numery rodziców, pokolenie narodzin i zastosowane operatory. Elita
+// i osobniki, które przeżyły zastępowanie, zachowują swój numer – po tym
+// widać, że to kopia sprzed pokoleń, a nie nowe dziecko.
+//
+// `Genealogy` to archiwum osobników z kilku ostatnich pokoleń, z którego
+// odtwarza się drzewo przodków wybranego chromosomu.
+// ---------------------------------------------------------------------------
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::Chromosome;
+use crate::population::Population;
+
+/// Pochodzenie chromosomu.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Origin {
+    /// Numer osobnika, unikalny w obrębie przebiegu.
+    pub id:        u64,
+    /// Numery rodziców (brak dla populacji startowej).
+    pub parents:   Option<(u64, u64)>,
+    /// Pokolenie, w którym osobnik powstał.
+    pub birth:     usize,
+    /// Punkty cięcia krzyżowania: pozycje, na których dziecko zmienia rodzica,
+    /// od którego bierze bity (patrz [`Origin::cut_points`]); puste dla
+    /// reprezentacji rzeczywistej. `None`, gdy krzyżowanie nie zaszło i dziecko
+    /// jest kopią rodzica.
+    pub crossover: Option<Vec<usize>>,
+    /// Indeksy zmutowanych bitów (genów w reprezentacji rzeczywistej).
+    pub mutations: Vec<usize>,
+}
+
+impl Origin {
+    /// Punkty cięcia odczytane z maski zamian krzyżowania: pozycje, na których
+    /// dziecko zmienia rodzica, od którego bierze bity.
+    pub fn cut_points(mask: &[bool]) -> Vec<usize> {
+        (0..mask.len()).filter(|&i| mask[i] != (i > 0 && mask[i - 1])).collect()
+    }
+
+    /// Opis zastosowanych operatorów, np. "krzyżowanie (cięcia: 3, 9), mutacje: 12".
+    pub fn operators_str(&self) -> String {
+        if self.parents.is_none() {
+            return "populacja startowa".into();
+        }
+        let join = |v: &[usize]| v.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(", ");
+        let crossover = match &self.crossover {
+            None                          => "kopia rodzica".to_string(),
+            Some(cuts) if cuts.is_empty() => "krzyżowanie".to_string(),
+            Some(cuts)                    => format!("krzyżowanie (cięcia: {})", join(cuts)),
+        };
+        if self.mutations.is_empty() {
+            crossover
+        } else {
+            format!("{crossover}, mutacje: {}", join(&self.mutations))
+        }
+    }
+}
+
+/// Archiwum osobników z ostatnich `depth` pokoleń (plus wszystkich, którzy
+/// wciąż żyją) – wystarcza do narysowania `depth` pokoleń przodków.
+#[derive(Clone, Debug)]
+pub struct Genealogy {
+    records: HashMap<u64, Chromosome>,
+    depth:   usize,
+}
+
+impl Genealogy {
+    /// Domyślna liczba pokoleń przechowywanych w archiwum.
+    pub const DEFAULT_DEPTH: usize = 12;
+
+    /// Zaczyna archiwum od populacji startowej.
+    pub fn new(pop: &Population, depth: usize) -> Self {
+        let mut genealogy = Self { records: HashMap::new(), depth };
+        genealogy.record(pop);
+        genealogy
+    }
+
+    /// Dopisuje nowe osobniki populacji i usuwa za stare, już martwe.
+    pub fn record(&mut self, pop: &Population) {
+        for c in &pop.chromosomes {
+            self.records.entry(c.origin.id).or_insert_with(|| c.clone());
+        }
+        let alive: HashSet<u64> = pop.chromosomes.iter().map(|c| c.origin.id).collect();
+        let oldest = pop.generation.saturating_sub(self.depth);
+        self.records.retain(|id, c| c.origin.birth >= oldest || alive.contains(id));
+    }
+
+    /// Osobnik o podanym numerze, o ile jest jeszcze w archiwum.
+    pub fn get(&self, id: u64) -> Option<&Chromosome> {
+        self.records.get(&id)
+    }
+
+    /// Kopia tej części archiwum, która opisuje osobnika `id` i jego przodków
+    /// (np. żeby narysować drzewo bez trzymania blokady stanu GA).
+    pub fn ancestry(&self, id: u64) -> Genealogy {
+        let mut records = HashMap::new();
+        let mut pending = vec![id];
+        while let Some(id) = pending.pop() {
+            if records.contains_key(&id) {
+                continue;
+            }
+            let Some(c) = self.get(id) else { continue };
+            if let Some((a, b)) = c.origin.parents {
+                pending.extend([a, b]);
+            }
+            records.insert(id, c.clone());
+        }
+        Self { records, depth: self.depth }
+    }
+
+    /// Rodzice osobnika (brakujący w archiwum są pomijani).
+    pub fn parents(&self, id: u64) -> Vec<&Chromosome> {
+        let Some((a, b)) = self.get(id).and_then(|c| c.origin.parents) else {
+            return Vec::new();
+        };
+        let ids = if a == b { vec![a] } else { vec![a, b] };
+        ids.into_iter().filter_map(|p| self.get(p)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromosome::VarSpec;
+    use crate::fitness::FitnessMode;
+
+    fn individual(id: u64, parents: Option<(u64, u64)>, birth: usize) -> Chromosome {
+        let mut c = Chromosome::from_xs(vec![0.0], &[VarSpec::default()]);
+        c.origin = Origin { id, parents, birth, ..Origin::default() };
+        c
+    }
+
+    fn pop(generation: usize, chromosomes: Vec<Chromosome>) -> Population {
+        Population::from_chromosomes(chromosomes, generation, &FitnessMode::default())
+    }
+
+    #[test]
+    fn cut_points_are_read_from_the_swap_mask() {
+        assert_eq!(Origin::cut_points(&[false, false, true, true, false]), [2, 4]);
+        assert_eq!(Origin::cut_points(&[true, true]), [0]);
+        assert!(Origin::cut_points(&[false; 3]).is_empty());
+    }
+
+    #[test]
+    fn operators_are_described() {
+        assert_eq!(Origin::default().operators_str(), "populacja startowa");
+        let child = Origin { parents: Some((1, 2)), crossover: Some(vec![3, 9]), mutations: vec![12], ..Origin::default() };
+        assert_eq!(child.operators_str(), "krzyżowanie (cięcia: 3, 9), mutacje: 12");
+        let copy = Origin { parents: Some((1, 1)), ..Origin::default() };
+        assert_eq!(copy.operators_str(), "kopia rodzica");
+    }
+
+    #[test]
+    fn archive_keeps_recent_and_living_individuals() {
+        let mut genealogy = Genealogy::new(&pop(0, vec![individual(0, None, 0), individual(1, None, 0)]), 2);
+        genealogy.record(&pop(1, vec![individual(1, None, 0), individual(2, Some((0, 1)), 1)]));
+        genealogy.record(&pop(3, vec![individual(1, None, 0), individual(3, Some((2, 2)), 3)]));
+        // #0 urodził się 3 pokolenia temu i już nie żyje; #1 wciąż żyje.
+        assert!(genealogy.get(0).is_none());
+        assert!(genealogy.get(1).is_some());
+        assert_eq!(genealogy.parents(2).iter().map(|c| c.origin.id).collect::<Vec<_>>(), [1]);
+        assert_eq!(genealogy.parents(3).len(), 1);
+        assert!(genealogy.parents(1).is_empty());
+    }
+
+    #[test]
+    fn ancestry_copies_only_the_ancestors() {
+        let start = pop(0, vec![individual(0, None, 0), individual(1, None, 0), individual(2, None, 0)]);
+        let mut genealogy = Genealogy::new(&start, 10);
+        genealogy.record(&pop(1, vec![individual(3, Some((0, 1)), 1), individual(4, Some((2, 2)), 1)]));
+        genealogy.record(&pop(2, vec![individual(5, Some((3, 3)), 2), individual(6, Some((4, 3)), 2)]));
+
+        let ancestry = genealogy.ancestry(5);
+        let mut ids: Vec<u64> = ancestry.records.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, [0, 1, 3, 5]);
+        assert!(genealogy.ancestry(99).records.is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+// ---------------------------------------------------------------------------
+// Historia pokoleń
+//
+// Populacje kolejnych pokoleń przebiegu, żeby można było wrócić do dowolnego
+// z nich (suwak pod wykresem). Pamięć jest ograniczona: pojemność wynika
+// z budżetu bajtów i szacowanego rozmiaru jednego pokolenia (liczba osobników,
+// długość genomu, liczba zmiennych), a po jej przekroczeniu najstarsze
+// pokolenia są usuwane (bufor cykliczny).
+// ---------------------------------------------------------------------------
+
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+use crate::chromosome::{genome_len, Chromosome, Representation};
+use crate::params::OptionsParams;
+use crate::population::Population;
+
+/// Bufor cykliczny ostatnich populacji przebiegu.
+#[derive(Clone, Debug)]
+pub struct History {
+    populations: VecDeque<Population>,
+    capacity:    usize,
+}
+
+impl History {
+    /// Domyślny budżet pamięci historii w bajtach.
+    pub const DEFAULT_BUDGET: usize = 64 * 1024 * 1024;
+
+    /// Pusta historia mieszcząca najwyżej `capacity` pokoleń (co najmniej jedno).
+    pub fn new(capacity: usize) -> Self {
+        Self { populations: VecDeque::new(), capacity: capacity.max(1) }
+    }
+
+    /// Pusta historia, której pokolenia przy parametrach `params` zmieszczą
+    /// się w `budget` bajtach.
+    pub fn with_budget(budget: usize, params: &OptionsParams) -> Self {
+        Self::new(Self::capacity_for(budget, params))
+    }
+
+    /// Liczba pokoleń mieszczących się w `budget` bajtach (co najmniej jedno).
+    pub fn capacity_for(budget: usize, params: &OptionsParams) -> usize {
+        let genes = match params.representation {
+            Representation::Binary => genome_len(&params.vars),
+            Representation::Real   => 0,
+        };
+        // Chromosom: sama struktura, bity genomu, zdekodowane zmienne i listy
+        // w `Origin` (punkty cięcia, mutacje) – szacowane na kilka pozycji.
+        const ORIGIN_ENTRIES: usize = 8;
+        let chromosome = size_of::<Chromosome>()
+            + genes * size_of::<bool>()
+            + params.vars.len() * size_of::<f64>()
+            + ORIGIN_ENTRIES * size_of::<usize>();
+        let generation = size_of::<Population>() + params.pop_size * chromosome;
+        (budget / generation).max(1)
+    }
+
+    /// Największa liczba przechowywanych pokoleń.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Zmienia pojemność (co najmniej jedno pokolenie), usuwając najstarsze
+    /// pokolenia, które się już nie mieszczą.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.populations.len() > self.capacity {
+            self.populations.pop_front();
+        }
+    }
+
+    /// Dopisuje pokolenie, usuwając najstarsze, gdy brak miejsca.
+    pub fn push(&mut self, pop: Population) {
+        if self.populations.len() == self.capacity {
+            self.populations.pop_front();
+        }
+        self.populations.push_back(pop);
+    }
+
+    /// Populacja pokolenia `generation`, o ile jest jeszcze w historii.
+    pub fn get(&self, generation: usize) -> Option<&Population> {
+        let first = self.populations.front()?.generation;
+        self.populations
+            .get(generation.checked_sub(first)?)
+            .filter(|p| p.generation == generation)
+    }
+
+    /// Numery najstarszego i najnowszego zapamiętanego pokolenia.
+    pub fn range(&self) -> Option<(usize, usize)> {
+        Some((self.populations.front()?.generation, self.populations.back()?.generation))
+    }
+
+    /// Wszystkie zapamiętane pokolenia od najstarszego (np. do przeliczenia
+    /// fitness po zmianie kierunku optymalizacji).
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Population> {
+        self.populations.iter_mut()
+    }
+
+    /// Liczba zapamiętanych pokoleń.
+    pub fn len(&self) -> usize {
+        self.populations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.populations.is_empty()
+    }
+
+    /// Usuwa wszystkie pokolenia (np. przed nowym przebiegiem).
+    pub fn clear(&mut self) {
+        self.populations.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generation(n: usize) -> Population {
+        Population { chromosomes: Vec::new(), generation: n, next_id: 0 }
+    }
+
+    #[test]
+    fn oldest_generations_are_dropped_past_capacity() {
+        let mut history = History::new(3);
+        assert!(history.is_empty());
+        assert_eq!(history.range(), None);
+        for n in 0..5 {
+            history.push(generation(n));
+        }
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.range(), Some((2, 4)));
+        assert!(history.get(1).is_none());
+        assert_eq!(history.get(3).map(|p| p.generation), Some(3));
+        assert!(history.get(5).is_none());
+    }
+
+    #[test]
+    fn capacity_is_at_least_one() {
+        let mut history = History::new(0);
+        history.push(generation(7));
+        history.push(generation(8));
+        assert_eq!(history.range(), Some((8, 8)));
+    }
+
+    #[test]
+    fn get_finds_generations_after_a_gap() {
+        // Wczytany przebieg zaczyna historię od dalszego pokolenia.
+        let mut history = History::new(10);
+        history.push(generation(40));
+        history.push(generation(41));
+        assert_eq!(history.get(41).map(|p| p.generation), Some(41));
+        assert!(history.get(0).is_none());
+    }
+
+    #[test]
+    fn capacity_follows_the_memory_budget() {
+        let small = OptionsParams::default();
+        let large = OptionsParams { pop_size: 10_000, ..OptionsParams::default() };
+        let budget = History::DEFAULT_BUDGET;
+        let (few, many) = (History::capacity_for(budget, &large), History::capacity_for(budget, &small));
+        assert!(few < many / 100, "{few} vs {many}");
+        assert!(many > 1000);
+
+        let mut wide = large.clone();
+        wide.vars[0].bits = 64;
+        assert!(History::capacity_for(budget, &wide) < few);
+        assert_eq!(History::capacity_for(0, &large), 1);
+        assert_eq!(History::with_budget(budget, &large).capacity(), few);
+    }
+
+    #[test]
+    fn shrinking_capacity_drops_oldest() {
+        let mut history = History::new(5);
+        for n in 0..5 {
+            history.push(generation(n));
+        }
+        history.set_capacity(2);
+        assert_eq!(history.range(), Some((3, 4)));
+        history.set_capacity(0);
+        assert_eq!((history.capacity(), history.range()), (1, Some((4, 4))));
+    }
+
+    #[test]
+    fn iter_mut_visits_all_and_clear_empties() {
+        let mut history = History::new(4);
+        for n in 0..3 {
+            history.push(generation(n));
+        }
+        history.iter_mut().for_each(|p| p.next_id = 5);
+        assert!((0..3).all(|n| history.get(n).unwrap().next_id == 5));
+        history.clear();
+        assert!(history.is_empty());
+    }
+}
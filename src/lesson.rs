@@ -0,0 +1,337 @@
+use eframe::egui;
+use egui::text::LayoutJob;
+use egui::{Color32, FontId, TextFormat};
+use genetictool2::{Chromosome, GaRng, OptionsParams, Population, SelectionMethod, StepTrace};
+
+// ---------------------------------------------------------------------------
+// Lesson – tryb nauki: jedno pokolenie rozpisane na kroki
+//
+// Pokolenie liczone jest z góry przez `Engine::step_explained` na kopii
+// populacji i generatora – tym samym kodem i tymi samymi losowaniami co
+// "Następna generacja" – więc kroki pokazują dokładnie to, co się stało.
+// Wstecz/Dalej tylko przeglądają zapis; wynik trafia do GA dopiero po
+// zakończeniu lekcji.
+//
+// Typowe użycie:
+//   match lesson.show(ctx) {
+//       LessonAction::Finish => { /* zapisz lesson.result i lesson.rng */ }
+//       LessonAction::Cancel => { /* porzuć lekcję */ }
+//       LessonAction::None   => {}
+//   }
+// ---------------------------------------------------------------------------
+
+const PARENT_A: Color32 = Color32::from_rgb(60, 120, 200);
+const PARENT_B: Color32 = Color32::from_rgb(220, 140, 0);
+const WINNER:   Color32 = Color32::from_rgb(220, 60, 60);
+const RIVAL:    Color32 = Color32::from_rgb(230, 200, 60);
+const CHILD:    Color32 = Color32::from_rgb(60, 180, 90);
+
+/// Punkt wyróżniony na wykresie funkcji w bieżącym kroku lekcji.
+pub struct Mark {
+    pub xs:    Vec<f64>,
+    pub value: f64,
+    pub color: Color32,
+    pub label: String,
+}
+
+/// Co zrobić z lekcją po narysowaniu okna.
+pub enum LessonAction {
+    None,
+    /// Przejdź do obliczonego pokolenia.
+    Finish,
+    /// Porzuć lekcję – populacja i generator zostają bez zmian.
+    Cancel,
+}
+
+/// Krok lekcji; liczby to indeksy losowań / kojarzeń w `StepTrace`.
+enum LessonStep {
+    Start,
+    Draw(usize),
+    Crossover(usize),
+    Mutation(usize),
+    Survivors,
+}
+
+pub struct Lesson {
+    /// Populacja przed pokoleniem – indeksy w zapisie dotyczą jej.
+    parents:     Population,
+    /// Populacja po pokoleniu (taka sama, jaką dałby `Engine::step`).
+    pub result:  Population,
+    /// Generator po pokoleniu – przebieg toczy się dalej tym strumieniem.
+    pub rng:     GaRng,
+    /// Numer przebiegu, w którym policzono lekcję (patrz `GaState::run_epoch`).
+    pub epoch:   u64,
+    params:      OptionsParams,
+    trace:       StepTrace,
+    steps:       Vec<LessonStep>,
+    current:     usize,
+}
+
+impl Lesson {
+    pub fn new(
+        parents: Population,
+        result: Population,
+        rng: GaRng,
+        trace: StepTrace,
+        params: OptionsParams,
+        epoch: u64,
+    ) -> Self {
+        let mut steps = vec![LessonStep::Start];
+        for i in 0..trace.matings.len() {
+            steps.push(LessonStep::Draw(2 * i));
+            steps.push(LessonStep::Draw(2 * i + 1));
+            steps.push(LessonStep::Crossover(i));
+            steps.push(LessonStep::Mutation(i));
+        }
+        steps.push(LessonStep::Survivors);
+        Self { parents, result, rng, epoch, params, trace, steps, current: 0 }
+    }
+
+    fn parent(&self, index: usize) -> &Chromosome {
+        &self.parents.chromosomes[index]
+    }
+
+    fn children(&self, mating: usize) -> Vec<&Chromosome> {
+        self.trace.matings[mating].children.iter().map(|&i| &self.trace.children[i]).collect()
+    }
+
+    /// Punkty wyróżnione na wykresie w bieżącym kroku.
+    pub fn marks(&self) -> Vec<Mark> {
+        let mark = |c: &Chromosome, color, label: String| Mark { xs: c.xs.clone(), value: c.value, color, label };
+        match self.steps[self.current] {
+            LessonStep::Start => Vec::new(),
+            LessonStep::Draw(d) => {
+                let draw = &self.trace.draws[d];
+                let mut marks: Vec<Mark> = draw.competitors
+                    .iter()
+                    .filter(|&&i| i != draw.winner)
+                    .map(|&i| mark(self.parent(i), RIVAL, String::new()))
+                    .collect();
+                marks.push(mark(self.parent(draw.winner), WINNER, format!("#{}", self.parent(draw.winner).origin.id)));
+                marks
+            }
+            LessonStep::Crossover(m) | LessonStep::Mutation(m) => {
+                let (a, b) = self.trace.matings[m].parents;
+                let mut marks = vec![
+                    mark(self.parent(a), PARENT_A, "A".into()),
+                    mark(self.parent(b), PARENT_B, "B".into()),
+                ];
+                if matches!(self.steps[self.current], LessonStep::Mutation(_)) {
+                    for (k, child) in self.children(m).into_iter().enumerate() {
+                        marks.push(mark(child, CHILD, format!("{}", k + 1)));
+                    }
+                }
+                marks
+            }
+            LessonStep::Survivors => self.result.chromosomes
+                .iter()
+                .filter(|c| c.origin.birth < self.result.generation)
+                .map(|c| mark(c, PARENT_B, format!("#{}", c.origin.id)))
+                .collect(),
+        }
+    }
+
+    /// Rysuje okno lekcji z opisem bieżącego kroku i przyciskami Wstecz/Dalej.
+    pub fn show(&mut self, ctx: &egui::Context) -> LessonAction {
+        let mut action = LessonAction::None;
+        let mut open = true;
+        let last = self.steps.len() - 1;
+        egui::Window::new(format!("Krok po kroku – pokolenie {}", self.result.generation))
+            .open(&mut open)
+            .default_width(520.0)
+            .default_pos(egui::pos2(24.0, 64.0))
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(format!("Krok {} z {}", self.current + 1, self.steps.len())).small());
+                ui.separator();
+                self.describe(ui);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(self.current > 0, egui::Button::new("◀ Wstecz")).clicked() {
+                        self.current -= 1;
+                    }
+                    if self.current < last {
+                        if ui.button("Dalej ▶").clicked() {
+                            self.current += 1;
+                        }
+                        if ui.button("Do końca").on_hover_text("Przejdź do ostatniego kroku").clicked() {
+                            self.current = last;
+                        }
+                    } else if ui.button("Zakończ pokolenie").clicked() {
+                        action = LessonAction::Finish;
+                    }
+                });
+            });
+        if !open {
+            action = LessonAction::Cancel;
+        }
+        action
+    }
+
+    fn describe(&self, ui: &mut egui::Ui) {
+        let text_color = ui.visuals().text_color();
+        match self.steps[self.current] {
+            LessonStep::Start => {
+                ui.label(format!(
+                    "Pokolenie {} → {}: {}.",
+                    self.parents.generation,
+                    self.result.generation,
+                    self.params.operators_summary()
+                ));
+                ui.label(format!(
+                    "Powstanie {} dzieci z {} par rodziców. Każda para to dwa losowania selekcji, \
+                     krzyżowanie i mutacja; na końcu zastępowanie wybiera nową populację.",
+                    self.trace.children.len(),
+                    self.trace.matings.len()
+                ));
+            }
+            LessonStep::Draw(d) => {
+                let draw = &self.trace.draws[d];
+                let which = if d % 2 == 0 { "A" } else { "B" };
+                ui.label(egui::RichText::new(format!("Selekcja rodzica {which} pary {}", d / 2 + 1)).strong());
+                let winner = self.parent(draw.winner);
+                if self.params.selection.method == SelectionMethod::Tournament {
+                    ui.label(format!(
+                        "Turniej: losujemy {} osobników, wygrywa ten z najwyższym fitness.",
+                        draw.competitors.len()
+                    ));
+                    for &i in &draw.competitors {
+                        let c = self.parent(i);
+                        let color = if i == draw.winner { WINNER } else { RIVAL };
+                        let row = format!("#{:<5} fit={:9.4}  x={}", c.origin.id, c.fitness, c.xs_str().trim());
+                        ui.label(
+                            egui::RichText::new(row)
+                                .monospace()
+                                .color(color),
+                        );
+                    }
+                } else {
+                    ui.label(format!("Metoda: {}.", self.params.selection.method.label()));
+                }
+                ui.label(format!(
+                    "Wybrany: #{} (miejsce {} w rankingu), f={:.4}",
+                    winner.origin.id,
+                    draw.winner + 1,
+                    winner.value
+                ));
+            }
+            LessonStep::Crossover(m) => {
+                let (a, b) = self.trace.matings[m].parents;
+                let (pa, pb) = (self.parent(a), self.parent(b));
+                let children = self.children(m);
+                ui.label(egui::RichText::new(format!("Krzyżowanie pary {}", m + 1)).strong());
+                let Some(cuts) = children[0].origin.crossover.clone() else {
+                    ui.label(format!(
+                        "Krzyżowanie nie zaszło (zachodzi z prawdopodobieństwem {}) – dzieci są kopiami rodziców.",
+                        self.params.crossover_prob
+                    ));
+                    return;
+                };
+                if pa.genes.is_empty() {
+                    ui.label(format!("Operator: {}.", self.params.real.crossover.label()));
+                    ui.label(egui::RichText::new(format!("rodzic A  x={}", pa.xs_str().trim())).monospace().color(PARENT_A));
+                    ui.label(egui::RichText::new(format!("rodzic B  x={}", pb.xs_str().trim())).monospace().color(PARENT_B));
+                    return;
+                }
+                let cut_list: Vec<String> = cuts.iter().map(|c| c.to_string()).collect();
+                ui.label(format!(
+                    "Operator: {}; punkty cięcia: {}. Dzieci biorą na zmianę odcinki od obu rodziców.",
+                    self.params.crossover.method.label(),
+                    cut_list.join(", ")
+                ));
+                ui.label(bits_job("rodzic A   ", &pa.genes, &cuts, &[], |_| PARENT_A, text_color));
+                ui.label(bits_job("rodzic B   ", &pb.genes, &cuts, &[], |_| PARENT_B, text_color));
+                for (k, child) in children.iter().enumerate() {
+                    let before = unmutated(child);
+                    // Dziecko 1 zaczyna od rodzica A, dziecko 2 od rodzica B;
+                    // każde cięcie zmienia rodzica.
+                    let source = |i: usize| {
+                        let swaps = cuts.iter().filter(|&&c| c <= i).count();
+                        if (swaps + k) % 2 == 0 { PARENT_A } else { PARENT_B }
+                    };
+                    ui.label(bits_job(&format!("dziecko {}  ", k + 1), &before, &cuts, &[], source, text_color));
+                }
+            }
+            LessonStep::Mutation(m) => {
+                ui.label(egui::RichText::new(format!("Mutacja dzieci pary {}", m + 1)).strong());
+                ui.label(format!(
+                    "Każdy gen zmienia się z prawdopodobieństwem {}.",
+                    self.params.mutation_prob
+                ));
+                for (k, child) in self.children(m).into_iter().enumerate() {
+                    let mutations = &child.origin.mutations;
+                    if child.genes.is_empty() {
+                        let genes: Vec<String> = mutations.iter().map(|i| format!("x{}", i + 1)).collect();
+                        let changed = if genes.is_empty() { "brak".to_string() } else { genes.join(", ") };
+                        ui.label(format!("dziecko {}: zmutowane geny: {changed}", k + 1));
+                    } else {
+                        let plain = |_| text_color;
+                        let before = unmutated(child);
+                        ui.label(bits_job(&format!("dziecko {} przed ", k + 1), &before, &[], mutations, plain, text_color));
+                        ui.label(bits_job(&format!("dziecko {} po    ", k + 1), &child.genes, &[], mutations, plain, text_color));
+                    }
+                    let summary = format!("#{}  x={}  f={:.4}", child.origin.id, child.xs_str().trim(), child.value);
+                    ui.label(egui::RichText::new(summary).monospace().color(CHILD));
+                }
+            }
+            LessonStep::Survivors => {
+                ui.label(egui::RichText::new("Zastępowanie").strong());
+                let survivors: Vec<String> = self.result.chromosomes
+                    .iter()
+                    .filter(|c| c.origin.birth < self.result.generation)
+                    .map(|c| format!("#{}", c.origin.id))
+                    .collect();
+                ui.label(format!(
+                    "Strategia: {}; elity: {}. Z poprzedniego pokolenia przechodzi bez zmian: {}.",
+                    self.params.replacement.strategy.label(),
+                    self.trace.elites,
+                    if survivors.is_empty() { "nikt".to_string() } else { survivors.join(", ") }
+                ));
+                if let Some(best) = self.result.best() {
+                    ui.label(format!(
+                        "Najlepszy w pokoleniu {}: #{}  x={}  f={:.4}",
+                        self.result.generation,
+                        best.origin.id,
+                        best.xs_str().trim(),
+                        best.value
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Geny dziecka sprzed mutacji (zmutowane bity odwrócone z powrotem).
+fn unmutated(child: &Chromosome) -> Vec<bool> {
+    let mut genes = child.genes.clone();
+    for &i in &child.origin.mutations {
+        genes[i] = !genes[i];
+    }
+    genes
+}
+
+/// Ciąg bitów z etykietą: `|` przed punktami cięcia, kolor bitu według
+/// `color_of`, bity z `flipped` na czerwonym tle.
+fn bits_job(
+    label: &str,
+    genes: &[bool],
+    cuts: &[usize],
+    flipped: &[usize],
+    color_of: impl Fn(usize) -> Color32,
+    text_color: Color32,
+) -> LayoutJob {
+    let font = FontId::monospace(12.0);
+    let mut job = LayoutJob::default();
+    job.append(label, 0.0, TextFormat::simple(font.clone(), text_color));
+    for (i, &bit) in genes.iter().enumerate() {
+        if i > 0 && cuts.contains(&i) {
+            job.append("|", 0.0, TextFormat::simple(font.clone(), text_color));
+        }
+        let mut format = TextFormat::simple(font.clone(), color_of(i));
+        if flipped.contains(&i) {
+            format.color = Color32::WHITE;
+            format.background = WINNER;
+        }
+        job.append(if bit { "1" } else { "0" }, 0.0, format);
+    }
+    job
+}
@@ -0,0 +1,44 @@
+//! Silnik algorytmu genetycznego niezależny od GUI.
+//!
+//! Aplikacja eframe (`main.rs`) jedynie wywołuje [`Engine::step`], dzięki czemu
+//! ten sam algorytm można uruchamiać z testów, skryptów i innych narzędzi.
+//!
+//! Licencja: MIT
+
+pub mod chromosome;
+pub mod crossover;
+pub mod document;
+pub mod encoding;
+pub mod engine;
+pub mod export;
+pub mod expr;
+pub mod fitness;
+pub mod genealogy;
+pub mod history;
+pub mod objective;
+pub mod params;
+pub mod population;
+pub mod real;
+pub mod replacement;
+pub mod selection;
+pub mod stats;
+pub mod stopping;
+
+pub use chromosome::{Chromosome, Representation, VarSpec, BITS, MAX_BITS, MIN_BITS};
+pub use crossover::{Crossover, CrossoverMethod, CrossoverParams};
+pub use document::RunDocument;
+pub use encoding::Encoding;
+pub use engine::{rng_from_seed, Engine, GaRng, Mating, StepTrace};
+pub use export::ExportFormat;
+pub use expr::{Expr, ParseError};
+pub use fitness::{Direction, FitnessMode, FitnessTransform};
+pub use genealogy::{Genealogy, Origin};
+pub use history::History;
+pub use objective::Objective;
+pub use params::OptionsParams;
+pub use population::Population;
+pub use real::{RealCrossover, RealMutation, RealOperators};
+pub use replacement::{Replacement, ReplacementParams};
+pub use selection::{Draw, SelectionMethod, SelectionParams, Selector};
+pub use stats::GenerationStats;
+pub use stopping::{Criterion, StopCriteria, StopMonitor, StopReason};
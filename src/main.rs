@@ -1,94 +1,34 @@
-/// Licencja: MIT
+// Licencja: MIT
 
+mod cli;
+mod lesson;
 mod options_window;
-use options_window::{OptionsParams, OptionsWindow};
+mod render;
+mod settings;
+use lesson::{Lesson, LessonAction, Mark};
+use options_window::OptionsWindow;
+use render::{PlotFormat, RasterImage, RenderTarget, SvgTarget};
+use settings::{Settings, Theme};
 
 use eframe::egui;
 use egui::{vec2, Color32, FontId, Layout, Painter, Pos2, Rect, Sense, Stroke, Ui, Vec2, Widget};
-use rand::Rng;
-use rand::SeedableRng;
+use genetictool2::chromosome::genome_len;
+use genetictool2::export;
+use genetictool2::{
+    document, objective, rng_from_seed, Chromosome, Engine, ExportFormat, FitnessMode, GaRng, Genealogy,
+    GenerationStats, History, Objective, OptionsParams, ParseError, Population, Representation, RunDocument,
+    StopMonitor, StopReason, VarSpec,
+};
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-/// Liczba bitów kodujących jeden chromosom (chromosom = wartość X).
-/// 16 bitów daje rozdzielczość ~0.0003 na dziedzinie [-10, 10].
-const BITS: usize = 16;
+/// Jak często najwyżej odświeżamy okno podczas obliczeń (~30 klatek/s) –
+/// niezależnie od tego, ile pokoleń na sekundę liczy GA.
+const REPAINT_INTERVAL: Duration = Duration::from_millis(33);
 
-/// Pojedynczy chromosom: ciąg bitów reprezentujący wartość X z dziedziny funkcji.
-///
-/// Bity interpretowane są jako liczba całkowita bez znaku [0, 2^BITS),
-/// a następnie liniowo mapowane na przedział [x_min, x_max].
-#[derive(Clone, Debug)]
-struct Chromosome {
-    /// Geny – ciąg `BITS` bitów.
-    genes: [bool; BITS],
-    /// Wartość fitness (f(x)) obliczona dla tego chromosomu.
-    fitness: f64,
-    /// Wartość X zdekodowana z genów.
-    x: f64,
-}
-
-impl Chromosome {
-    /// Tworzy chromosom z losowych bitów w dziedzinie [x_min, x_max].
-    fn random<R: Rng>(x_min: f64, x_max: f64, rng: &mut R) -> Self {
-        let mut genes = [false; BITS];
-        for bit in genes.iter_mut() {
-            *bit = rng.gen_bool(0.5);
-        }
-        let x = Self::decode(&genes, x_min, x_max);
-        Self { genes, fitness: 0.0, x }
-    }
-
-    /// Dekoduje ciąg bitów na wartość X w dziedzinie [x_min, x_max].
-    fn decode(genes: &[bool; BITS], x_min: f64, x_max: f64) -> f64 {
-        let max_val = ((1u64 << BITS) - 1) as f64;
-        let int_val: u64 = genes.iter().fold(0u64, |acc, &b| (acc << 1) | b as u64);
-        x_min + (int_val as f64 / max_val) * (x_max - x_min)
-    }
-
-    /// Oblicza i zapisuje fitness dla podanej funkcji celu.
-    fn evaluate(&mut self, func: fn(f64) -> f64) {
-        self.fitness = func(self.x);
-    }
-
-    /// Zwraca czytelny podgląd: bity (pierwsze 8 skrócone) + x + fitness.
-    fn display_str(&self) -> String {
-        let bits: String = self.genes.iter().map(|&b| if b { '1' } else { '0' }).collect();
-        format!("{}  x={:7.4}  f={:8.4}", bits, self.x, self.fitness)
-    }
-}
-
-// ---------------------------------------------------------------------------
-
-/// Cała populacja: zbiór chromosomów + metadane bieżącego pokolenia.
-#[derive(Clone, Debug)]
-struct Population {
-    chromosomes: Vec<Chromosome>,
-    /// Numer aktualnego pokolenia (0 = populacja startowa).
-    generation: usize,
-}
-
-impl Population {
-    /// Tworzy losową populację startową.
-    fn random(size: usize, x_min: f64, x_max: f64, func: fn(f64) -> f64) -> Self {
-        let mut rng = rand::rngs::StdRng::seed_from_u64(12345);
-        let mut chromosomes: Vec<Chromosome> = (0..size)
-            .map(|_| {
-                let mut c = Chromosome::random(x_min, x_max, &mut rng);
-                c.evaluate(func);
-                c
-            })
-            .collect();
-
-        // Sortujemy malejąco po fitness – najlepszy na górze listy.
-        chromosomes.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
-        Self { chromosomes, generation: 0 }
-    }
-
-    /// Zwraca najlepszy chromosom (po sortowaniu zawsze pierwszy).
-    fn best(&self) -> Option<&Chromosome> {
-        self.chromosomes.first()
-    }
-}
+/// Domyślna przerwa między pokoleniami w trybie Auto.
+const DEFAULT_AUTO_DELAY_MS: u64 = 500;
 
 // ---------------------------------------------------------------------------
 // Kolory używane w całym wykresie
@@ -225,15 +165,19 @@ fn nice_step(span: f64, target_count: f64) -> f64 {
     nice * scale
 }
 
+/// Górny limit liczby podziałek jednej osi.
+const MAX_TICKS: usize = 200;
+
+/// Podziałki `lo + i·step` będące wielokrotnościami `step`. Liczba podziałek
+/// jest wyliczana z góry, więc przy dużych wartościach (gdzie `t + step == t`)
+/// pętla nie może się zawiesić.
 fn ticks_for(lo: f64, hi: f64, step: f64) -> Vec<f64> {
-    let first = (lo / step).ceil() * step;
-    let mut v = Vec::new();
-    let mut t = first;
-    while t <= hi + step * 1e-9 {
-        if t >= lo - step * 1e-9 { v.push(t); }
-        t += step;
+    if !(step > 0.0 && lo.is_finite() && hi.is_finite() && lo <= hi) {
+        return Vec::new();
     }
-    v
+    let first = (lo / step - 1e-9).ceil();
+    let count = ((hi / step + 1e-9).floor() - first + 1.0).clamp(0.0, MAX_TICKS as f64) as usize;
+    (0..count).map(|i| (first + i as f64) * step).collect()
 }
 
 fn fmt_tick(v: f64, step: f64) -> String {
@@ -242,7 +186,7 @@ fn fmt_tick(v: f64, step: f64) -> String {
     else             { format!("{:.prec$}", v, prec = decimals) }
 }
 
-fn draw_dashed_line(painter: &Painter, from: Pos2, to: Pos2, stroke: Stroke) {
+fn draw_dashed_line(target: &impl RenderTarget, from: Pos2, to: Pos2, stroke: Stroke) {
     let dash = 4.0_f32;
     let gap  = 4.0_f32;
     let delta = to - from;
@@ -252,62 +196,62 @@ fn draw_dashed_line(painter: &Painter, from: Pos2, to: Pos2, stroke: Stroke) {
     let mut d = 0.0_f32;
     while d < len {
         let d_end = (d + dash).min(len);
-        painter.line_segment([from + dir * d, from + dir * d_end], stroke);
+        target.line_segment([from + dir * d, from + dir * d_end], stroke);
         d += dash + gap;
     }
 }
 
-fn draw_background(painter: &Painter, layout: &PlotLayout, colors: &PlotColors) {
-    painter.rect_filled(layout.rect,      0.0, colors.margin);
-    painter.rect_filled(layout.plot_rect, 0.0, colors.bg);
+fn draw_background(target: &impl RenderTarget, layout: &PlotLayout, colors: &PlotColors) {
+    target.rect_filled(layout.rect,      0.0, colors.margin);
+    target.rect_filled(layout.plot_rect, 0.0, colors.bg);
 }
 
-fn draw_grid(painter: &Painter, layout: &PlotLayout, colors: &PlotColors) {
+fn draw_grid(target: &impl RenderTarget, layout: &PlotLayout, colors: &PlotColors) {
     let stroke = Stroke::new(1.0, colors.grid);
     for &xv in &layout.x_ticks {
         let sx = layout.x_to_screen(xv);
-        painter.line_segment(
+        target.line_segment(
             [Pos2::new(sx, layout.plot_rect.top()), Pos2::new(sx, layout.plot_rect.bottom())],
             stroke,
         );
     }
     for &yv in &layout.y_ticks {
         let sy = layout.y_to_screen(yv);
-        painter.line_segment(
+        target.line_segment(
             [Pos2::new(layout.plot_rect.left(), sy), Pos2::new(layout.plot_rect.right(), sy)],
             stroke,
         );
     }
 }
 
-fn draw_zero_axes(painter: &Painter, layout: &PlotLayout, colors: &PlotColors) {
+fn draw_zero_axes(target: &impl RenderTarget, layout: &PlotLayout, colors: &PlotColors) {
     let stroke = Stroke::new(1.0, colors.axis);
     if layout.y_min <= 0.0 && layout.y_max >= 0.0 {
         let sy = layout.y_to_screen(0.0);
-        painter.line_segment(
+        target.line_segment(
             [Pos2::new(layout.plot_rect.left(), sy), Pos2::new(layout.plot_rect.right(), sy)],
             stroke,
         );
     }
     if layout.x_min <= 0.0 && layout.x_max >= 0.0 {
         let sx = layout.x_to_screen(0.0);
-        painter.line_segment(
+        target.line_segment(
             [Pos2::new(sx, layout.plot_rect.top()), Pos2::new(sx, layout.plot_rect.bottom())],
             stroke,
         );
     }
 }
 
-fn draw_ticks_and_labels(painter: &Painter, layout: &PlotLayout, colors: &PlotColors) {
+fn draw_ticks_and_labels(target: &impl RenderTarget, layout: &PlotLayout, colors: &PlotColors) {
     let tick_stroke = Stroke::new(1.0, colors.tick);
     let tl          = layout.tick_len;
     for &xv in &layout.x_ticks {
         let sx = layout.x_to_screen(xv);
-        painter.line_segment(
+        target.line_segment(
             [Pos2::new(sx, layout.plot_rect.bottom()), Pos2::new(sx, layout.plot_rect.bottom() + tl)],
             tick_stroke,
         );
-        painter.text(
+        target.text(
             Pos2::new(sx, layout.plot_rect.bottom() + tl + 1.0),
             egui::Align2::CENTER_TOP,
             fmt_tick(xv, layout.x_step),
@@ -317,11 +261,11 @@ fn draw_ticks_and_labels(painter: &Painter, layout: &PlotLayout, colors: &PlotCo
     }
     for &yv in &layout.y_ticks {
         let sy = layout.y_to_screen(yv);
-        painter.line_segment(
+        target.line_segment(
             [Pos2::new(layout.plot_rect.left() - tl, sy), Pos2::new(layout.plot_rect.left(), sy)],
             tick_stroke,
         );
-        painter.text(
+        target.text(
             Pos2::new(layout.plot_rect.left() - tl - 2.0, sy),
             egui::Align2::RIGHT_CENTER,
             fmt_tick(yv, layout.y_step),
@@ -331,7 +275,7 @@ fn draw_ticks_and_labels(painter: &Painter, layout: &PlotLayout, colors: &PlotCo
     }
 }
 
-fn draw_curve(painter: &Painter, layout: &PlotLayout, colors: &PlotColors, eval: impl Fn(f64) -> f64) {
+fn draw_curve(target: &impl RenderTarget, layout: &PlotLayout, colors: &PlotColors, eval: impl Fn(f64) -> f64) {
     let stroke = Stroke::new(1.5, colors.curve);
     let cols   = layout.plot_rect.width() as usize;
     let x_span = layout.x_max - layout.x_min;
@@ -343,7 +287,7 @@ fn draw_curve(painter: &Painter, layout: &PlotLayout, colors: &PlotColors, eval:
         if y.is_finite() {
             let p = layout.to_screen(x, y);
             if let Some(prev_p) = prev {
-                painter.line_segment([prev_p, p], stroke);
+                target.line_segment([prev_p, p], stroke);
             }
             prev = Some(p);
         } else {
@@ -354,15 +298,15 @@ fn draw_curve(painter: &Painter, layout: &PlotLayout, colors: &PlotColors, eval:
 
 /// Rysuje punkty populacji jako pionowe kreski na krzywej.
 fn draw_population_on_curve(
-    painter: &Painter,
+    target: &impl RenderTarget,
     layout: &PlotLayout,
     population: &Population,
     _colors: &PlotColors,
 ) {
     // Najlepszy chromosom – złota gwiazdka, reszta – niebieskie krople.
     for (i, chrom) in population.chromosomes.iter().enumerate() {
-        let x = chrom.x;
-        let y = chrom.fitness;
+        let x = chrom.xs[0];
+        let y = chrom.value;
         if !x.is_finite() || !y.is_finite() { continue; }
         // Rysuj tylko jeśli mieści się w bieżącym zakresie osi.
         if x < layout.x_min || x > layout.x_max { continue; }
@@ -376,11 +320,122 @@ fn draw_population_on_curve(
             // Reszta - niebieski dostosowany do motywu
             (Color32::from_rgb(60, 120, 200), 3.0_f32)
         };
-        painter.circle_filled(p, radius, color);
+        target.circle_filled(p, radius, color);
     }
 }
 
-fn draw_crosshair(painter: &Painter, layout: &PlotLayout, colors: &PlotColors, hx: f64, hy: f64) {
+/// Rysuje populację jako punkty (x, y) na mapie funkcji dwóch zmiennych.
+fn draw_population_on_map(target: &impl RenderTarget, layout: &PlotLayout, population: &Population) {
+    // Najlepszy rysowany na końcu, żeby nie zasłoniła go reszta populacji.
+    for (i, chrom) in population.chromosomes.iter().enumerate().rev() {
+        let [x, y, ..] = chrom.xs[..] else { continue };
+        if !x.is_finite() || !y.is_finite() { continue; }
+        if x < layout.x_min || x > layout.x_max { continue; }
+        if y < layout.y_min || y > layout.y_max { continue; }
+
+        let p = layout.to_screen(x, y);
+        let (color, radius) = if i == 0 {
+            (Color32::from_rgb(220, 140, 0), 5.0_f32)
+        } else {
+            (Color32::from_rgb(60, 120, 200), 3.0_f32)
+        };
+        // Obwódka – kropki muszą być widoczne na każdym kolorze mapy.
+        target.circle(p, radius, color, Stroke::new(1.0, Color32::from_gray(250)));
+    }
+}
+
+/// Rysuje punkty wyróżnione przez tryb nauki: obwódka i podpis.
+/// `point` wybiera współrzędne wykresu (x, f) albo (x, y) punktu.
+fn draw_marks(
+    target: &impl RenderTarget,
+    layout: &PlotLayout,
+    colors: &PlotColors,
+    marks: &[Mark],
+    point: impl Fn(&Mark) -> (f64, f64),
+) {
+    for mark in marks {
+        let (x, y) = point(mark);
+        if !x.is_finite() || !y.is_finite() { continue; }
+        let pos = layout.to_screen(x, y);
+        if !layout.plot_rect.expand(4.0).contains(pos) { continue; }
+        target.circle_stroke(pos, 7.0, Stroke::new(2.5, mark.color));
+        if !mark.label.is_empty() {
+            let text_pos = pos + vec2(0.0, -10.0);
+            let size = target.text_size(&mark.label, &layout.font);
+            target.rect_filled(
+                Rect::from_center_size(text_pos - vec2(0.0, size.y * 0.5), size + vec2(4.0, 2.0)),
+                2.0,
+                colors.crosshair_bg,
+            );
+            target.text(text_pos, egui::Align2::CENTER_BOTTOM, &mark.label, layout.font.clone(), mark.color);
+        }
+    }
+}
+
+/// Mapa kolorów zbliżona do "viridis": t ∈ [0, 1] → kolor.
+fn colormap(t: f64) -> Color32 {
+    const STOPS: [(u8, u8, u8); 5] = [
+        (68, 1, 84),
+        (59, 82, 139),
+        (33, 145, 140),
+        (94, 201, 98),
+        (253, 231, 37),
+    ];
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (t.floor() as usize).min(STOPS.len() - 2);
+    let f = t - i as f64;
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+    let (a, b) = (STOPS[i], STOPS[i + 1]);
+    Color32::from_rgb(lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+}
+
+/// Izolinie metodą "marching squares" na siatce wartości `z` (nx × ny,
+/// wiersz 0 = góra). Zwraca odcinki we współrzędnych siatki (kolumna, wiersz).
+fn contour_segments(z: &[f64], nx: usize, ny: usize, levels: &[f64]) -> Vec<[(f64, f64); 2]> {
+    let mut out = Vec::new();
+    let at = |i: usize, j: usize| z[j * nx + i];
+    for &level in levels {
+        for j in 0..ny.saturating_sub(1) {
+            for i in 0..nx.saturating_sub(1) {
+                // Narożniki: a – lewy górny, b – prawy górny, c – prawy dolny, d – lewy dolny.
+                let (a, b, c, d) = (at(i, j), at(i + 1, j), at(i + 1, j + 1), at(i, j + 1));
+                if !(a.is_finite() && b.is_finite() && c.is_finite() && d.is_finite()) {
+                    continue;
+                }
+                let (fi, fj) = (i as f64, j as f64);
+                let cross = |v1: f64, v2: f64| (level - v1) / (v2 - v1);
+                let top    = ((a >= level) != (b >= level)).then(|| (fi + cross(a, b), fj));
+                let right  = ((b >= level) != (c >= level)).then(|| (fi + 1.0, fj + cross(b, c)));
+                let bottom = ((d >= level) != (c >= level)).then(|| (fi + cross(d, c), fj + 1.0));
+                let left   = ((a >= level) != (d >= level)).then(|| (fi, fj + cross(a, d)));
+
+                match (top, right, bottom, left) {
+                    // Punkt siodłowy: o połączeniu decyduje wartość w środku kwadratu.
+                    (Some(t), Some(r), Some(bo), Some(l)) => {
+                        let center = (a + b + c + d) * 0.25;
+                        if (a >= level) == (center >= level) {
+                            out.push([t, r]);
+                            out.push([bo, l]);
+                        } else {
+                            out.push([l, t]);
+                            out.push([r, bo]);
+                        }
+                    }
+                    _ => {
+                        let pts: Vec<(f64, f64)> =
+                            [top, right, bottom, left].into_iter().flatten().collect();
+                        if let [p, q] = pts[..] {
+                            out.push([p, q]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn draw_crosshair(target: &impl RenderTarget, layout: &PlotLayout, colors: &PlotColors, hx: f64, hy: f64) {
     let sx = layout.x_to_screen(hx);
     let sy = layout.y_to_screen(hy);
     let center = Pos2::new(sx, sy);
@@ -389,15 +444,15 @@ fn draw_crosshair(painter: &Painter, layout: &PlotLayout, colors: &PlotColors, h
 
     // Kolor przerywanych linii dostosowany do motywu
     let dot_stroke = Stroke::new(1.0, colors.grid);
-    draw_dashed_line(painter, Pos2::new(pr.left(), sy), Pos2::new(sx, sy),          dot_stroke);
-    draw_dashed_line(painter, Pos2::new(sx, sy),        Pos2::new(pr.right(), sy),  dot_stroke);
-    draw_dashed_line(painter, Pos2::new(sx, pr.top()),  Pos2::new(sx, sy),          dot_stroke);
-    draw_dashed_line(painter, Pos2::new(sx, sy),        Pos2::new(sx, pr.bottom()), dot_stroke);
+    draw_dashed_line(target, Pos2::new(pr.left(), sy), Pos2::new(sx, sy),          dot_stroke);
+    draw_dashed_line(target, Pos2::new(sx, sy),        Pos2::new(pr.right(), sy),  dot_stroke);
+    draw_dashed_line(target, Pos2::new(sx, pr.top()),  Pos2::new(sx, sy),          dot_stroke);
+    draw_dashed_line(target, Pos2::new(sx, sy),        Pos2::new(sx, pr.bottom()), dot_stroke);
 
     let cross_stroke = Stroke::new(1.5, colors.crosshair);
     let arm = 5.0_f32;
-    painter.line_segment([center - vec2(arm, 0.0), center + vec2(arm, 0.0)], cross_stroke);
-    painter.line_segment([center - vec2(0.0, arm), center + vec2(0.0, arm)], cross_stroke);
+    target.line_segment([center - vec2(arm, 0.0), center + vec2(arm, 0.0)], cross_stroke);
+    target.line_segment([center - vec2(0.0, arm), center + vec2(0.0, arm)], cross_stroke);
 
     let font = FontId::monospace(layout.font_size);
     let color = colors.crosshair;
@@ -405,46 +460,229 @@ fn draw_crosshair(painter: &Painter, layout: &PlotLayout, colors: &PlotColors, h
 
     let x_label = format!("{:.3}", hx);
     let x_label_pos = Pos2::new(sx, pr.bottom() + tl + 1.0);
-    let x_galley = painter.layout_no_wrap(x_label.clone(), font.clone(), color);
+    let x_size = target.text_size(&x_label, &font);
     let x_bg = Rect::from_center_size(
-        x_label_pos + vec2(0.0, x_galley.size().y * 0.5),
-        x_galley.size() + vec2(4.0, 2.0),
+        x_label_pos + vec2(0.0, x_size.y * 0.5),
+        x_size + vec2(4.0, 2.0),
     );
-    painter.rect_filled(x_bg, 2.0, bg);
-    painter.text(x_label_pos, egui::Align2::CENTER_TOP, x_label, font.clone(), color);
+    target.rect_filled(x_bg, 2.0, bg);
+    target.text(x_label_pos, egui::Align2::CENTER_TOP, x_label, font.clone(), color);
 
     let y_label = format!("{:.3}", hy);
     let y_label_pos = Pos2::new(pr.left() - tl - 2.0, sy);
-    let y_galley = painter.layout_no_wrap(y_label.clone(), font.clone(), color);
+    let y_size = target.text_size(&y_label, &font);
     let y_bg = Rect::from_center_size(
-        y_label_pos - vec2(y_galley.size().x * 0.5 + 2.0, 0.0),
-        y_galley.size() + vec2(4.0, 2.0),
+        y_label_pos - vec2(y_size.x * 0.5 + 2.0, 0.0),
+        y_size + vec2(4.0, 2.0),
     );
-    painter.rect_filled(y_bg, 2.0, bg);
-    painter.text(y_label_pos, egui::Align2::RIGHT_CENTER, y_label, font, color);
+    target.rect_filled(y_bg, 2.0, bg);
+    target.text(y_label_pos, egui::Align2::RIGHT_CENTER, y_label, font, color);
+}
+
+// ---------------------------------------------------------------------------
+// Mapa 2-D: siatka wartości f(x, y) liczona tylko po zmianie funkcji,
+// dziedziny lub rozmiaru wykresu (a nie w każdej klatce).
+// ---------------------------------------------------------------------------
+struct HeatmapCache {
+    /// Funkcja, dziedzina [x_min, x_max, y_min, y_max] i rozmiar siatki.
+    key:      (String, [f64; 4], [usize; 2]),
+    image:    RasterImage,
+    z_min:    f64,
+    z_max:    f64,
+    /// Izolinie – odcinki we współrzędnych danych (x, y).
+    contours: Vec<[(f64, f64); 2]>,
 }
 
 // ---------------------------------------------------------------------------
 // FunctionPlot
 // ---------------------------------------------------------------------------
 struct FunctionPlot {
-    func:  fn(f64) -> f64,
+    func:  Objective,
     x_min: f64,
     x_max: f64,
+    /// Dziedzina drugiej zmiennej – oś pionowa mapy 2-D.
+    y_min: f64,
+    y_max: f64,
+    /// Czy rysować izolinie na mapie 2-D.
+    contours: bool,
+    heatmap:  RefCell<Option<HeatmapCache>>,
 }
 
 impl FunctionPlot {
-    fn new(func: fn(f64) -> f64, x_min: f64, x_max: f64) -> Self {
-        Self { func, x_min, x_max }
+    fn new(func: Objective, vars: &[VarSpec]) -> Self {
+        let mut plot = Self {
+            func,
+            x_min: 0.0, x_max: 1.0,
+            y_min: 0.0, y_max: 1.0,
+            contours: true,
+            heatmap: RefCell::new(None),
+        };
+        plot.set_domain(vars);
+        plot
+    }
+
+    /// Osie wykresu biorą dziedziny z parametrów przebiegu.
+    fn set_domain(&mut self, vars: &[VarSpec]) {
+        if let Some(v) = vars.first() {
+            (self.x_min, self.x_max) = (v.min, v.max);
+        }
+        if let Some(v) = vars.get(1) {
+            (self.y_min, self.y_max) = (v.min, v.max);
+        }
+    }
+
+    /// Krzywą y = f(x) da się narysować tylko dla funkcji jednej zmiennej.
+    fn is_plottable(&self) -> bool {
+        self.func.dims() == 1
+    }
+
+    /// Mapę kolorów f(x, y) rysujemy dla funkcji dwóch zmiennych.
+    fn is_map(&self) -> bool {
+        self.func.dims() == 2
     }
 
-    /// Funkcja celu – do maksymalizacji przez GA.
-    fn target(x: f64) -> f64 {
-        (x + 5.0) * (2.0 * x - 5.0).cos() - 5.0
+    /// Przelicza siatkę wartości, obraz mapy i izolinie, jeśli zmieniła się
+    /// funkcja, dziedzina albo rozmiar wykresu.
+    fn update_heatmap(&self, plot_rect: Rect) {
+        // Komórka ~3 px: dość gęsto dla gładkiego obrazu, a siatka pozostaje tania.
+        let nx = ((plot_rect.width() / 3.0) as usize).clamp(16, 256);
+        let ny = ((plot_rect.height() / 3.0) as usize).clamp(16, 256);
+        let key = (
+            self.func.label().to_string(),
+            [self.x_min, self.x_max, self.y_min, self.y_max],
+            [nx, ny],
+        );
+        if self.heatmap.borrow().as_ref().is_some_and(|c| c.key == key) {
+            return;
+        }
+
+        // Wartości w środkach komórek; wiersz 0 to góra wykresu (y_max).
+        let mut z = Vec::with_capacity(nx * ny);
+        for j in 0..ny {
+            let y = self.y_max - (j as f64 + 0.5) / ny as f64 * (self.y_max - self.y_min);
+            for i in 0..nx {
+                let x = self.x_min + (i as f64 + 0.5) / nx as f64 * (self.x_max - self.x_min);
+                z.push(self.func.eval(&[x, y]));
+            }
+        }
+        let finite = z.iter().copied().filter(|v| v.is_finite());
+        let z_min = finite.clone().fold(f64::INFINITY, f64::min);
+        let z_max = finite.fold(f64::NEG_INFINITY, f64::max);
+        let z_span = if z_max > z_min { z_max - z_min } else { 1.0 };
+
+        let pixels = z
+            .iter()
+            .map(|&v| if v.is_finite() { colormap((v - z_min) / z_span) } else { Color32::TRANSPARENT })
+            .collect();
+        let image = RasterImage::new(egui::ColorImage::new([nx, ny], pixels));
+
+        // Izolinie na "ładnych" poziomach, jak podziałka osi.
+        let levels = if z_max > z_min {
+            ticks_for(z_min, z_max, nice_step(z_span, 10.0))
+        } else {
+            Vec::new()
+        };
+        let to_data = |(gi, gj): (f64, f64)| {
+            (
+                self.x_min + (gi + 0.5) / nx as f64 * (self.x_max - self.x_min),
+                self.y_max - (gj + 0.5) / ny as f64 * (self.y_max - self.y_min),
+            )
+        };
+        let contours = contour_segments(&z, nx, ny, &levels)
+            .into_iter()
+            .map(|[p, q]| [to_data(p), to_data(q)])
+            .collect();
+
+        *self.heatmap.borrow_mut() = Some(HeatmapCache { key, image, z_min, z_max, contours });
+    }
+
+    /// Mapa kolorów f(x, y) z izoliniami, populacją i krzyżem kursora (x, y, f).
+    fn paint_map(
+        &self,
+        target: &impl RenderTarget,
+        rect: Rect,
+        colors: &PlotColors,
+        hover: Option<(f64, f64)>,
+        overlay: &PlotOverlay,
+    ) {
+        let layout = PlotLayout::new(rect, self.x_min, self.x_max, self.y_min, self.y_max);
+        if layout.plot_rect.width() < 2.0 || layout.plot_rect.height() < 2.0 {
+            return;
+        }
+        self.update_heatmap(layout.plot_rect);
+        let cache = self.heatmap.borrow();
+        let Some(cache) = cache.as_ref() else { return };
+
+        draw_background(target, &layout, colors);
+        target.image(layout.plot_rect, &cache.image);
+        draw_ticks_and_labels(target, &layout, colors);
+
+        if self.contours {
+            let stroke = Stroke::new(1.0, Color32::from_rgba_unmultiplied(255, 255, 255, 110));
+            for [(x1, y1), (x2, y2)] in &cache.contours {
+                target.line_segment([layout.to_screen(*x1, *y1), layout.to_screen(*x2, *y2)], stroke);
+            }
+        }
+
+        // Legenda: pasek kolorów z wartościami skrajnymi w prawym górnym rogu.
+        let bar = Rect::from_min_size(
+            Pos2::new(layout.plot_rect.right() - 138.0, layout.plot_rect.top() + 8.0),
+            vec2(130.0, 10.0),
+        );
+        let legend_h = layout.font_size + 4.0;
+        target.rect_filled(
+            Rect::from_min_max(bar.min - vec2(4.0, 4.0), bar.max + vec2(4.0, legend_h + 2.0)),
+            2.0,
+            colors.crosshair_bg,
+        );
+        let slices = 40;
+        for k in 0..slices {
+            let x0 = bar.left() + bar.width() * k as f32 / slices as f32;
+            let x1 = bar.left() + bar.width() * (k + 1) as f32 / slices as f32;
+            target.rect_filled(
+                Rect::from_min_max(Pos2::new(x0, bar.top()), Pos2::new(x1 + 0.5, bar.bottom())),
+                0.0,
+                colormap((k as f64 + 0.5) / slices as f64),
+            );
+        }
+        let z_step = nice_step((cache.z_max - cache.z_min).max(1e-12), 4.0);
+        target.text(
+            bar.left_bottom() + vec2(0.0, 2.0),
+            egui::Align2::LEFT_TOP,
+            fmt_tick(cache.z_min, z_step),
+            layout.font.clone(),
+            colors.label,
+        );
+        target.text(
+            bar.right_bottom() + vec2(0.0, 2.0),
+            egui::Align2::RIGHT_TOP,
+            fmt_tick(cache.z_max, z_step),
+            layout.font.clone(),
+            colors.label,
+        );
+
+        if let Some(pop) = overlay.population {
+            draw_population_on_map(target, &layout, pop);
+        }
+        draw_marks(target, &layout, colors, overlay.marks, |m| (m.xs[0], m.xs[1]));
+
+        if let Some((hx, hy)) = hover {
+            draw_crosshair(target, &layout, colors, hx, hy);
+            let f = self.func.eval(&[hx, hy]);
+            let text = format!("f = {f:.4}");
+            let pos = layout.to_screen(hx, hy) + vec2(10.0, -10.0);
+            let size = target.text_size(&text, &layout.font);
+            target.rect_filled(
+                Rect::from_min_size(pos - vec2(2.0, size.y + 1.0), size + vec2(4.0, 2.0)),
+                2.0,
+                colors.crosshair_bg,
+            );
+            target.text(pos, egui::Align2::LEFT_BOTTOM, text, layout.font.clone(), colors.crosshair);
+        }
     }
 
     fn eval(&self, x: f64) -> f64 {
-        (self.func)(x)
+        self.func.eval(&[x])
     }
 
     fn y_range(&self, steps: usize) -> (f64, f64) {
@@ -464,57 +702,91 @@ impl FunctionPlot {
     }
 
     fn paint(&self,
-        ui: &Ui,
-        painter: &Painter,
+        target: &impl RenderTarget,
         rect: Rect,
+        colors: &PlotColors,
         hover: Option<(f64, f64)>,
-        population: Option<&Population>
+        overlay: &PlotOverlay,
     ) {
         if rect.width() < 4.0 || rect.height() < 4.0 { return; }
 
+        if self.is_map() {
+            self.paint_map(target, rect, colors, hover, overlay);
+            return;
+        }
+
+        if !self.is_plottable() {
+            target.rect_filled(rect, 0.0, colors.bg);
+            target.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!(
+                    "Wykres dostępny dla funkcji jednej lub dwóch zmiennych (liczba zmiennych: {})",
+                    self.func.dims()
+                ),
+                FontId::proportional(14.0),
+                colors.label,
+            );
+            return;
+        }
+
         let cols = rect.width() as usize;
         let (y_min, y_max) = self.y_range(cols * 4);
         let layout = PlotLayout::new(rect, self.x_min, self.x_max, y_min, y_max);
 
-        // Automatyczne wykrywanie motywu z egui
-        let colors = if ui.visuals().dark_mode {
-            PlotColors::default_dark()
-        } else {
-            PlotColors::default_light()
-        };
-
         if layout.plot_rect.width() < 2.0 || layout.plot_rect.height() < 2.0 {
             return;
         }
 
-        draw_background(painter, &layout, &colors);
-        draw_grid(painter, &layout, &colors);
-        draw_zero_axes(painter, &layout, &colors);
-        draw_ticks_and_labels(painter, &layout, &colors);
-        draw_curve(painter, &layout, &colors, |x| self.eval(x));
+        draw_background(target, &layout, colors);
+        draw_grid(target, &layout, colors);
+        draw_zero_axes(target, &layout, colors);
+        draw_ticks_and_labels(target, &layout, colors);
+        draw_curve(target, &layout, colors, |x| self.eval(x));
 
         // Rysuj populację na krzywej (jeśli istnieje).
-        if let Some(pop) = population {
-            draw_population_on_curve(painter, &layout, pop, &colors);
+        if let Some(pop) = overlay.population {
+            draw_population_on_curve(target, &layout, pop, colors);
         }
+        draw_marks(target, &layout, colors, overlay.marks, |m| (m.xs[0], m.value));
 
         if let Some((hx, hy)) = hover {
-            draw_crosshair(painter, &layout, &colors, hx, hy);
+            draw_crosshair(target, &layout, colors, hx, hy);
         }
     }
+
+    /// Wykres jako dokument SVG o rozmiarze `size` (w jednostkach SVG),
+    /// bez kursora – do zapisu w pliku.
+    fn to_svg(&self, size: Vec2, colors: &PlotColors, overlay: &PlotOverlay) -> String {
+        let svg = SvgTarget::new(size);
+        self.paint(&svg, svg.rect(), colors, None, overlay);
+        svg.finish()
+    }
 }
 
 // ---------------------------------------------------------------------------
 // FunctionPlotWidget
 // ---------------------------------------------------------------------------
 struct FunctionPlotWidget<'a> {
-    plot:       &'a FunctionPlot,
+    plot:    &'a FunctionPlot,
+    overlay: PlotOverlay<'a>,
+}
+
+/// Co rysujemy na wykresie funkcji oprócz samej funkcji.
+struct PlotOverlay<'a> {
     population: Option<&'a Population>,
+    /// Punkty wyróżnione przez tryb nauki.
+    marks:      &'a [Mark],
 }
 
 impl<'a> FunctionPlotWidget<'a> {
     fn new(plot: &'a FunctionPlot, population: Option<&'a Population>) -> Self {
-        Self { plot, population }
+        Self { plot, overlay: PlotOverlay { population, marks: &[] } }
+    }
+
+    fn with_marks(mut self, marks: &'a [Mark]) -> Self {
+        self.overlay.marks = marks;
+        self
     }
 }
 
@@ -529,6 +801,18 @@ impl<'a> Widget for FunctionPlotWidget<'a> {
             let hover = response.hover_pos().and_then(|pos| {
                 if !layout.plot_rect.contains(pos) { return None; }
 
+                // Mapa 2-D: kursor wskazuje punkt (x, y) dziedziny, f liczy paint().
+                if self.plot.is_map() {
+                    let map = PlotLayout::new(rect, self.plot.x_min, self.plot.x_max, self.plot.y_min, self.plot.y_max);
+                    let tx = ((pos.x - map.plot_rect.left()) / map.plot_rect.width()) as f64;
+                    let ty = ((pos.y - map.plot_rect.top()) / map.plot_rect.height()) as f64;
+                    return Some((
+                        self.plot.x_min + tx * (self.plot.x_max - self.plot.x_min),
+                        self.plot.y_max - ty * (self.plot.y_max - self.plot.y_min),
+                    ));
+                }
+                if !self.plot.is_plottable() { return None; }
+
                 // pw: szerokość wykresu w pikselach
                 let pw = layout.plot_rect.width() as f64;
                 let hx = self.plot.x_min
@@ -548,59 +832,592 @@ impl<'a> Widget for FunctionPlotWidget<'a> {
                 ui.ctx().request_repaint();
             }
 
-            self.plot.paint(ui, ui.painter(), rect, hover, self.population);
+            // Automatyczne wykrywanie motywu z egui
+            let colors = if ui.visuals().dark_mode {
+                PlotColors::default_dark()
+            } else {
+                PlotColors::default_light()
+            };
+            self.plot.paint(ui.painter(), rect, &colors, hover, &self.overlay);
+        }
+
+        response
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Wykresy historii przebiegu: zbieżność (statystyki f) i różnorodność
+// ---------------------------------------------------------------------------
+const SERIES_BEST:    Color32 = Color32::from_rgb(220, 140, 0);
+const SERIES_MEDIAN:  Color32 = Color32::from_rgb(170, 90, 200);
+const SERIES_WORST:   Color32 = Color32::from_rgb(60, 120, 200);
+const SERIES_HAMMING: Color32 = Color32::from_rgb(210, 80, 80);
+const SERIES_SPREAD:  Color32 = Color32::from_rgb(60, 170, 170);
+
+/// Który wykres historii pokazać pod wykresem funkcji.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HistoryChart {
+    Hidden,
+    Convergence,
+    Diversity,
+}
+
+impl HistoryChart {
+    const ALL: [HistoryChart; 3] = [HistoryChart::Hidden, HistoryChart::Convergence, HistoryChart::Diversity];
+
+    fn label(self) -> &'static str {
+        match self {
+            HistoryChart::Hidden      => "Brak",
+            HistoryChart::Convergence => "Zbieżność",
+            HistoryChart::Diversity   => "Różnorodność",
+        }
+    }
+}
+
+/// Seria wykresu historii: nazwa w legendzie, kolor i odczyt wartości.
+type Series = (&'static str, Color32, Box<dyn Fn(&GenerationStats) -> f64>);
+
+struct HistoryChartWidget<'a> {
+    history:  &'a [GenerationStats],
+    kind:     HistoryChart,
+    /// Rozmiar populacji – mianownik ułamka unikalnych genotypów.
+    pop_size: usize,
+    /// Pokolenie oglądane na osi czasu (zaznaczane pionową linią).
+    selected: Option<usize>,
+}
+
+impl<'a> HistoryChartWidget<'a> {
+    fn new(history: &'a [GenerationStats], kind: HistoryChart, pop_size: usize) -> Self {
+        Self { history, kind, pop_size, selected: None }
+    }
+
+    /// Zaznacza pokolenie oglądane na osi czasu.
+    fn with_selected(mut self, generation: Option<usize>) -> Self {
+        self.selected = generation;
+        self
+    }
+
+    /// Serie wykresu, od rysowanej najpierw (pod spodem).
+    fn series(&self, colors: &PlotColors) -> Vec<Series> {
+        match self.kind {
+            HistoryChart::Hidden => Vec::new(),
+            HistoryChart::Convergence => vec![
+                ("najgorszy", SERIES_WORST,  Box::new(|s| s.worst)),
+                ("mediana",   SERIES_MEDIAN, Box::new(|s| s.median)),
+                ("średnia",   colors.curve,  Box::new(|s| s.mean)),
+                ("najlepszy", SERIES_BEST,   Box::new(|s| s.best)),
+            ],
+            HistoryChart::Diversity => {
+                let size = self.pop_size.max(1) as f64;
+                let mut series: Vec<Series> = vec![
+                    ("unikalne genotypy", SERIES_MEDIAN, Box::new(move |s| s.unique as f64 / size)),
+                    ("rozrzut x",         SERIES_SPREAD, Box::new(|s| s.spread)),
+                ];
+                // Odległość Hamminga ma sens tylko dla genomu bitowego.
+                if self.history.last().is_some_and(|s| !s.hamming.is_nan()) {
+                    series.push(("odległość Hamminga", SERIES_HAMMING, Box::new(|s| s.hamming)));
+                }
+                series
+            }
+        }
+    }
+
+    /// Zakres osi Y: dla zbieżności obejmuje wszystkie serie i pasmo
+    /// średnia ± σ, miary różnorodności to ułamki z [0, 1].
+    fn y_range(&self) -> (f64, f64) {
+        if self.kind == HistoryChart::Diversity {
+            return (0.0, 1.0);
+        }
+        let mut y_min = f64::MAX;
+        let mut y_max = f64::MIN;
+        for s in self.history {
+            for v in [s.best, s.worst, s.mean - s.std_dev, s.mean + s.std_dev] {
+                if v.is_finite() {
+                    y_min = y_min.min(v);
+                    y_max = y_max.max(v);
+                }
+            }
+        }
+        if y_min > y_max {
+            return (0.0, 1.0);
+        }
+        let pad = ((y_max - y_min) * 0.05).max(1e-9);
+        (y_min - pad, y_max + pad)
+    }
+
+    /// Co który punkt historii rysować, żeby nie rysować więcej niż ~2 na piksel.
+    fn stride(&self, layout: &PlotLayout) -> usize {
+        (self.history.len() / (2.0 * layout.plot_rect.width()).max(1.0) as usize).max(1)
+    }
+
+    /// Opis pokolenia pod kursorem.
+    fn tooltip(&self, s: &GenerationStats) -> String {
+        match self.kind {
+            HistoryChart::Diversity => format!(
+                "pokolenie {}\nHamming   {:.4}\nrozrzut x {:.4}\nunikalne  {}/{}",
+                s.generation, s.hamming, s.spread, s.unique, self.pop_size
+            ),
+            _ => format!(
+                "pokolenie {}\nnajlepszy {:.4}\nśrednia   {:.4}\nmediana   {:.4}\nnajgorszy {:.4}\nσ         {:.4}",
+                s.generation, s.best, s.mean, s.median, s.worst, s.std_dev
+            ),
+        }
+    }
+
+    fn paint(&self, ui: &Ui, painter: &Painter, rect: Rect, hover: Option<Pos2>) {
+        let colors = if ui.visuals().dark_mode {
+            PlotColors::default_dark()
+        } else {
+            PlotColors::default_light()
+        };
+        let (Some(first), Some(last)) = (self.history.first(), self.history.last()) else {
+            painter.rect_filled(rect, 0.0, colors.bg);
+            return;
+        };
+
+        let x_min = first.generation as f64;
+        let x_max = (last.generation as f64).max(x_min + 1.0);
+        let (y_min, y_max) = self.y_range();
+        let layout = PlotLayout::new(rect, x_min, x_max, y_min, y_max);
+        if layout.plot_rect.width() < 2.0 || layout.plot_rect.height() < 2.0 {
+            return;
+        }
+
+        draw_background(painter, &layout, &colors);
+        draw_grid(painter, &layout, &colors);
+        draw_zero_axes(painter, &layout, &colors);
+        draw_ticks_and_labels(painter, &layout, &colors);
+
+        let stride = self.stride(&layout);
+        let mut points: Vec<&GenerationStats> = self.history.iter().step_by(stride).collect();
+        if !(self.history.len() - 1).is_multiple_of(stride) {
+            points.push(last);
+        }
+
+        // Pasmo średnia ± σ – czworokąty między kolejnymi pokoleniami.
+        let band = colors.curve.gamma_multiply(0.18);
+        if self.kind == HistoryChart::Convergence {
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let corners = [
+                    (a.generation, a.mean + a.std_dev),
+                    (b.generation, b.mean + b.std_dev),
+                    (b.generation, b.mean - b.std_dev),
+                    (a.generation, a.mean - a.std_dev),
+                ];
+                if corners.iter().all(|(_, y)| y.is_finite()) {
+                    let poly = corners.iter().map(|&(g, y)| layout.to_screen(g as f64, y)).collect();
+                    painter.add(egui::Shape::convex_polygon(poly, band, Stroke::NONE));
+                }
+            }
+        }
+
+        let series = self.series(&colors);
+        for (_, color, value) in &series {
+            draw_series(painter, &layout, points.iter().map(|s| (s.generation as f64, value(s))), Stroke::new(1.5, *color));
+        }
+
+        // Oglądane pokolenie – ciągła linia pod legendą i kursorem.
+        if let Some(generation) = self.selected.filter(|&g| g as f64 >= x_min && g as f64 <= x_max) {
+            let sx = layout.x_to_screen(generation as f64);
+            painter.line_segment(
+                [Pos2::new(sx, layout.plot_rect.top()), Pos2::new(sx, layout.plot_rect.bottom())],
+                Stroke::new(1.5, colors.crosshair),
+            );
+        }
+
+        // Legenda w lewym górnym rogu.
+        let mut entries: Vec<(&str, Color32)> = series.iter().rev().map(|(name, color, _)| (*name, *color)).collect();
+        if self.kind == HistoryChart::Convergence {
+            entries.push(("średnia ± σ", band));
+        }
+        let line_h = layout.font_size + 3.0;
+        let legend = Rect::from_min_size(
+            layout.plot_rect.left_top() + vec2(6.0, 6.0),
+            vec2(layout.font_size * 12.0, line_h * entries.len() as f32 + 4.0),
+        );
+        painter.rect_filled(legend, 2.0, colors.crosshair_bg);
+        for (i, (name, color)) in entries.into_iter().enumerate() {
+            let y = legend.top() + 2.0 + line_h * (i as f32 + 0.5);
+            let x = legend.left() + 4.0;
+            painter.line_segment([Pos2::new(x, y), Pos2::new(x + 14.0, y)], Stroke::new(3.0, color));
+            painter.text(Pos2::new(x + 20.0, y), egui::Align2::LEFT_CENTER, name, layout.font.clone(), colors.label);
+        }
+
+        // Kursor: pionowa linia i wartości najbliższego pokolenia.
+        if let Some(pos) = hover.filter(|p| layout.plot_rect.contains(*p)) {
+            let t = ((pos.x - layout.plot_rect.left()) / layout.plot_rect.width()) as f64;
+            let generation = (x_min + t * (x_max - x_min)).round() as usize;
+            let idx = self.history
+                .partition_point(|s| s.generation < generation)
+                .min(self.history.len() - 1);
+            let s = &self.history[idx];
+            let sx = layout.x_to_screen(s.generation as f64);
+            draw_dashed_line(
+                painter,
+                Pos2::new(sx, layout.plot_rect.top()),
+                Pos2::new(sx, layout.plot_rect.bottom()),
+                Stroke::new(1.0, colors.crosshair),
+            );
+            let galley = painter.layout_no_wrap(self.tooltip(s), layout.font.clone(), colors.crosshair);
+            // Etykieta po tej stronie linii, po której jest więcej miejsca.
+            let x = if sx + 8.0 + galley.size().x < layout.plot_rect.right() {
+                sx + 8.0
+            } else {
+                sx - 8.0 - galley.size().x
+            };
+            let pos = Pos2::new(x, layout.plot_rect.top() + 6.0);
+            painter.rect_filled(
+                Rect::from_min_size(pos - vec2(2.0, 1.0), galley.size() + vec2(4.0, 2.0)),
+                2.0,
+                colors.crosshair_bg,
+            );
+            painter.galley(pos, galley, colors.crosshair);
+        }
+    }
+}
+
+/// Łamana przez punkty (x, y); wartości nieskończone przerywają linię.
+fn draw_series(painter: &Painter, layout: &PlotLayout, points: impl Iterator<Item = (f64, f64)>, stroke: Stroke) {
+    let mut line: Vec<Pos2> = Vec::new();
+    for (x, y) in points {
+        if y.is_finite() {
+            line.push(layout.to_screen(x, y));
+        } else if !line.is_empty() {
+            painter.add(egui::Shape::line(std::mem::take(&mut line), stroke));
+        }
+    }
+    if !line.is_empty() {
+        painter.add(egui::Shape::line(line, stroke));
+    }
+}
+
+impl<'a> Widget for HistoryChartWidget<'a> {
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        let available = ui.available_size();
+        let size = Vec2::new(available.x.max(2.0), available.y.max(2.0));
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            if response.hovered() {
+                ui.ctx().request_repaint();
+            }
+            self.paint(ui, ui.painter(), rect, response.hover_pos());
         }
 
         response
     }
 }
 
+// ---------------------------------------------------------------------------
+// Pasek częstości alleli: dla każdego bitu genomu słupek o wysokości równej
+// ułamkowi osobników z jedynką na tej pozycji. Słupki pełne albo puste to
+// pozycje utrwalone – cała populacja ma tam ten sam bit.
+// ---------------------------------------------------------------------------
+struct AlleleStrip<'a> {
+    freqs: &'a [f64],
+    /// Liczba bitów kolejnych zmiennych (separatory między odcinkami).
+    bits:  &'a [usize],
+}
+
+impl<'a> Widget for AlleleStrip<'a> {
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        let size = vec2(ui.available_width().max(2.0), 32.0);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        if self.freqs.is_empty() || !ui.is_rect_visible(rect) {
+            return response;
+        }
+        let colors = if ui.visuals().dark_mode {
+            PlotColors::default_dark()
+        } else {
+            PlotColors::default_light()
+        };
+        let painter = ui.painter();
+        painter.rect_filled(rect, 0.0, colors.bg);
+
+        let bar_w = rect.width() / self.freqs.len() as f32;
+        for (k, &p) in self.freqs.iter().enumerate() {
+            let x0 = rect.left() + bar_w * k as f32;
+            let top = rect.bottom() - rect.height() * p as f32;
+            painter.rect_filled(
+                Rect::from_min_max(Pos2::new(x0, top), Pos2::new(x0 + bar_w.max(1.0), rect.bottom())),
+                0.0,
+                colormap(p),
+            );
+        }
+        let mut offset = 0;
+        for &bits in &self.bits[..self.bits.len().saturating_sub(1)] {
+            offset += bits;
+            let x = rect.left() + bar_w * offset as f32;
+            painter.line_segment([Pos2::new(x, rect.top()), Pos2::new(x, rect.bottom())], Stroke::new(1.0, colors.axis));
+        }
+
+        match response.hover_pos() {
+            Some(pos) => {
+                let k = (((pos.x - rect.left()) / bar_w) as usize).min(self.freqs.len() - 1);
+                // Numer zmiennej i bitu w jej odcinku (bit 0 = najstarszy).
+                let (mut var, mut bit) = (0, k);
+                while var + 1 < self.bits.len() && bit >= self.bits[var] {
+                    bit -= self.bits[var];
+                    var += 1;
+                }
+                let text = format!("zmienna {}, bit {}: jedynki {:.0}%", var + 1, bit, self.freqs[k] * 100.0);
+                response.on_hover_text_at_pointer(text)
+            }
+            None => response,
+        }
+    }
+}
+
+/// Przykładowe funkcje celu dostępne z menu obok pola wyrażenia.
+/// Funkcje testowe wielu zmiennych mają minimum globalne – do minimalizacji.
+const EXAMPLES: &[(&str, &str)] = &[
+    ("Domyślna",                      objective::DEFAULT_SOURCE),
+    ("x·sin(x)",                      "x * sin(x)"),
+    ("Rastrigin 2D (min. w 0)",       "20 + x^2 - 10*cos(2*pi*x) + y^2 - 10*cos(2*pi*y)"),
+    ("Rosenbrock 2D (min. w (1, 1))", "100*(y - x^2)^2 + (1 - x)^2"),
+    ("Sfera 3D (min. w 0)",           "x1^2 + x2^2 + x3^2"),
+];
+
 /// Stan współdzielony między wątkiem GUI a wątkiem GA.
 /// Zamknięty w Arc<Mutex<>>, żeby oba wątki mogły go bezpiecznie czytać/pisać.
 struct GaState {
     population: Population,
+    /// Silnik GA (funkcja celu) używany przez wątki obliczeniowe.
+    engine: Engine,
+    /// Generator liczb losowych bieżącego przebiegu – jeden strumień od
+    /// populacji startowej aż po ostatnie pokolenie (bez ponownego seedowania).
+    rng: GaRng,
+    /// Ziarno, z którego wystartował bieżący przebieg (wyświetlane w panelu).
+    run_seed: u64,
+    /// Numer przebiegu, zwiększany przy każdym restarcie. Wątek GA, który
+    /// zaczął liczyć przed restartem, nie nadpisze wyniku nowego przebiegu.
+    run_epoch: u64,
     /// Czy trwa aktualnie obliczanie nowej generacji?
     running: bool,
     /// Czy włączony jest tryb auto?
-    /// Wątek pętlowy sprawdza tę flagę co sekundę.
+    /// Wątek pętlowy sprawdza tę flagę po każdym pokoleniu.
     auto_active: bool,
+    /// Czy trwa seria pokoleń ("Uruchom")? Zdjęcie flagi przerywa serię
+    /// po bieżącym pokoleniu.
+    batch_active: bool,
+    /// Przerwa między pokoleniami w trybie Auto (0 = najszybciej jak się da).
+    auto_delay: Duration,
     /// Czy wątek auto-calculate już działa?
     auto_thread_running: bool,
     /// Parametry GA edytowalne przez okno opcji.
     params: OptionsParams,
+    /// Pamięć warunków zatrzymania (stagnacja, czas) bieżącej serii lub przebiegu Auto.
+    stop_monitor: StopMonitor,
+    /// Dlaczego seria lub tryb Auto sam się zatrzymał (wyświetlane w panelu).
+    stop_reason: Option<StopReason>,
+    /// Statystyki wszystkich pokoleń bieżącego przebiegu (wykres zbieżności).
+    stats_history: Vec<GenerationStats>,
+    /// Zwiększany przy każdej zmianie już zapisanych statystyk (nowy przebieg,
+    /// przeliczenie fitness); samo dopisanie pokolenia go nie zmienia.
+    stats_revision: u64,
+    /// Archiwum osobników ostatnich pokoleń (okno rodowodu).
+    genealogy: Genealogy,
+    /// Populacje ostatnich pokoleń przebiegu (suwak osi czasu).
+    history: History,
+}
+
+impl GaState {
+    /// Rozpoczyna nowy przebieg: generator z ziarna z opcji i losowa
+    /// populacja startowa. Ten sam seed daje identyczny przebieg.
+    fn restart(&mut self) {
+        let seed = self.params.seed;
+        let mut rng = rng_from_seed(seed);
+        self.population = self.engine.random_population(&self.params, &mut rng);
+        self.rng = rng;
+        self.run_seed = seed;
+        self.run_epoch += 1;
+        self.stats_history = vec![GenerationStats::of(&self.population, &self.params.vars)];
+        self.stats_revision += 1;
+        self.genealogy = Genealogy::new(&self.population, Genealogy::DEFAULT_DEPTH);
+        self.history = History::with_budget(History::DEFAULT_BUDGET, &self.params);
+        self.history.push(self.population.clone());
+        self.start_stop_monitor();
+    }
+
+    /// Zastępuje bieżący przebieg wczytanym z pliku: funkcja celu, parametry,
+    /// populacja i generator. Bez zapisanej historii statystyki zaczynają
+    /// się od wczytanego pokolenia.
+    fn load(&mut self, doc: RunDocument, objective: Objective) -> Result<(), String> {
+        self.rng = doc.rng.restore()?;
+        self.engine.objective = objective;
+        self.params = doc.params;
+        self.population = doc.population;
+        self.run_seed = doc.run_seed;
+        self.run_epoch += 1;
+        self.stats_history = doc.history
+            .unwrap_or_else(|| vec![GenerationStats::of(&self.population, &self.params.vars)]);
+        self.stats_revision += 1;
+        self.genealogy = Genealogy::new(&self.population, Genealogy::DEFAULT_DEPTH);
+        self.history = History::with_budget(History::DEFAULT_BUDGET, &self.params);
+        self.history.push(self.population.clone());
+        self.start_stop_monitor();
+        Ok(())
+    }
+
+    /// Dokument bieżącego przebiegu do zapisu w pliku.
+    fn document(&self, with_history: bool) -> RunDocument {
+        RunDocument::new(
+            &self.engine.objective,
+            &self.params,
+            self.run_seed,
+            &self.rng,
+            &self.population,
+            with_history.then_some(self.stats_history.as_slice()),
+        )
+    }
+
+    /// Przyjmuje kolejne pokolenie przebiegu wraz z generatorem, który je
+    /// wylosował, i dopisuje je do historii statystyk, populacji i genealogii.
+    fn advance(&mut self, population: Population, rng: GaRng) {
+        self.stats_history.push(GenerationStats::of(&population, &self.params.vars));
+        self.genealogy.record(&population);
+        self.history.push(population.clone());
+        self.population = population;
+        self.rng = rng;
+    }
+
+    /// Zmienia tryb fitness, przeliczając bieżącą populację i całą historię
+    /// przebiegu. Statystyki pokoleń obecnych
+    /// w historii liczy od nowa; w starszych, których populacji już nie ma,
+    /// przy zmianie kierunku najlepszy i najgorszy zamieniają się miejscami
+    /// (przekształcenia fitness nie zmieniają kolejności osobników).
+    fn set_fitness_mode(&mut self, mode: FitnessMode) {
+        let flipped = self.params.fitness.direction != mode.direction;
+        self.params.fitness = mode;
+        self.population.rescore(&mode);
+        for population in self.history.iter_mut() {
+            population.rescore(&mode);
+        }
+        for stats in &mut self.stats_history {
+            if let Some(population) = self.history.get(stats.generation) {
+                *stats = GenerationStats::of(population, &self.params.vars);
+            } else if flipped {
+                std::mem::swap(&mut stats.best, &mut stats.worst);
+            }
+        }
+        self.stats_revision += 1;
+    }
+
+    /// Zaczyna od nowa liczenie warunków zatrzymania (przy starcie serii
+    /// lub trybu Auto i przy nowym przebiegu).
+    fn start_stop_monitor(&mut self) {
+        self.stop_monitor = StopMonitor::new(&self.population, &self.params.fitness);
+        self.stop_reason = None;
+    }
 }
 
 struct MyApp {
     plot: FunctionPlot,
     ga_state: Arc<Mutex<GaState>>,
     ctx: Option<egui::Context>,
-    selected_idx: Option<usize>,
+    /// Numer osobnika zaznaczonego na liście (jego rodowód pokazuje osobne okno).
+    selected_id: Option<u64>,
+    /// Przodkowie zaznaczonego osobnika skopiowani z archiwum, razem
+    /// z numerem przebiegu i osobnika, których dotyczą.
+    lineage: Option<(u64, u64, Genealogy)>,
     /// Zmierzona szerokość paska przycisków z poprzedniej klatki.
     /// Używana do obliczenia lewego marginesu centrującego.
     btn_bar_width: f32,
     /// Stan okna opcji (widoczność + wartości robocze w trakcie edycji).
     options_window: OptionsWindow,
+    /// Tekst funkcji celu w polu edycji (jeszcze niezatwierdzony).
+    expr_text: String,
+    /// Błąd parsowania `expr_text` (sprawdzany na bieżąco przy każdej zmianie).
+    expr_error: Option<ParseError>,
+    /// Przerwa między pokoleniami w trybie Auto ustawiana suwakiem "Tempo".
+    auto_delay_ms: u64,
+    /// Liczba pokoleń liczonych jednym kliknięciem "Uruchom".
+    batch_generations: usize,
+    /// Kopia `GaState::stats_history` do rysowania – uzupełniana tylko
+    /// o nowe pokolenia, całość kopiowana dopiero po zmianie `stats_revision`.
+    stats_cache: Vec<GenerationStats>,
+    stats_cache_revision: u64,
+    /// Wykres historii przebiegu pokazywany pod wykresem funkcji.
+    history_chart: HistoryChart,
+    /// Trwająca lekcja trybu "Krok po kroku".
+    lesson: Option<Lesson>,
+    /// Pokolenie wybrane suwakiem osi czasu (`None` = zawsze najnowsze).
+    view_generation: Option<usize>,
+    /// Czy zapisywać w pliku przebiegu historię statystyk.
+    save_history: bool,
+    /// Wynik ostatniego zapisu/odczytu pliku (komunikat albo błąd) w pasku menu.
+    file_status: Option<Result<String, String>>,
+    /// Rozmiar wykresu funkcji z ostatniej klatki – taki ma wyeksportowany wykres.
+    plot_size: Vec2,
+    /// Powiększenie eksportowanego obrazu PNG względem ekranu.
+    png_scale: f32,
+    /// Motyw wybrany w menu "Ustawienia".
+    theme: Theme,
+    /// Szerokość prawego panelu z ostatniej klatki (zapisywana w ustawieniach).
+    panel_width: f32,
+    /// Rozmiar wnętrza i położenie okna z ostatniej klatki.
+    window_size: Vec2,
+    window_pos: Option<Pos2>,
 }
 
-impl Default for MyApp {
-    fn default() -> Self {
-        let defaults = OptionsParams::default();
-        let pop = Population::random(defaults.pop_size, -10.0, 10.0, FunctionPlot::target);
+impl MyApp {
+    /// Okno z przebiegiem startującym z zapamiętanych ustawień.
+    fn new(settings: &Settings) -> Self {
+        let defaults = settings.params.clone();
+        let engine = Engine::new(settings.objective());
+        let mut rng = rng_from_seed(defaults.seed);
+        let pop = engine.random_population(&defaults, &mut rng);
+        let stop_monitor = StopMonitor::new(&pop, &defaults.fitness);
+        let stats_history = vec![GenerationStats::of(&pop, &defaults.vars)];
+        let genealogy = Genealogy::new(&pop, Genealogy::DEFAULT_DEPTH);
+        let mut history = History::with_budget(History::DEFAULT_BUDGET, &defaults);
+        history.push(pop.clone());
         let ga_state = Arc::new(Mutex::new(GaState {
             population: pop,
+            engine,
+            rng,
+            run_seed: defaults.seed,
+            run_epoch: 0,
             running: false,
             auto_active: false,
+            batch_active: false,
+            auto_delay: Duration::from_millis(DEFAULT_AUTO_DELAY_MS),
             auto_thread_running: false,
             params: defaults.clone(),
+            stop_monitor,
+            stop_reason: None,
+            stats_history,
+            stats_revision: 0,
+            genealogy,
+            history,
         }));
 
         Self {
-            plot: FunctionPlot::new(FunctionPlot::target, -10.0, 10.0),
+            plot: FunctionPlot::new(settings.objective(), &defaults.vars),
             ga_state,
             ctx: None,
-            selected_idx: None,
+            selected_id: None,
+            lineage: None,
             btn_bar_width: 0.0,
             options_window: OptionsWindow::new(&defaults),
+            expr_text: settings.objective.clone(),
+            expr_error: None,
+            auto_delay_ms: DEFAULT_AUTO_DELAY_MS,
+            batch_generations: 100,
+            stats_cache: Vec::new(),
+            stats_cache_revision: 0,
+            history_chart: HistoryChart::Convergence,
+            lesson: None,
+            view_generation: None,
+            save_history: true,
+            file_status: None,
+            plot_size: Vec2::new(1000.0, 600.0),
+            png_scale: 2.0,
+            theme: settings.theme,
+            panel_width: settings.panel_width,
+            window_size: Vec2::from(settings.window_size),
+            window_pos: settings.window_pos.map(Pos2::from),
         }
     }
 }
@@ -612,43 +1429,225 @@ impl eframe::App for MyApp {
             self.ctx = Some(ctx.clone());
         }
 
+        // Geometria okna do zapisania w ustawieniach; zapis przy zamykaniu.
+        let (inner, outer, closing) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (viewport.inner_rect, viewport.outer_rect, viewport.close_requested())
+        });
+        if let Some(inner) = inner {
+            self.window_size = inner.size();
+        }
+        if let Some(outer) = outer {
+            self.window_pos = Some(outer.min);
+        }
+        if closing && let Err(e) = self.settings().save() {
+            eprintln!("błąd: {e}");
+        }
+
         // Pobierz aktualny stan z mutexa (krótko, tylko żeby skopiować dane do wyświetlenia).
-        let (population_snapshot, ga_running) = {
+        // Populacja to pokolenie wybrane na osi czasu albo najnowsze.
+        let (
+            population_snapshot, timeline, history_capacity, ga_running, batch_active, run_seed, genome_info, operators,
+            stop_reason, var_bits,
+        ) = {
             let state = self.ga_state.lock().unwrap();
-            (state.population.clone(), state.running)
+            if self.stats_cache_revision != state.stats_revision
+                || self.stats_cache.len() > state.stats_history.len()
+            {
+                self.stats_cache.clear();
+                self.stats_cache_revision = state.stats_revision;
+            }
+            self.stats_cache.extend_from_slice(&state.stats_history[self.stats_cache.len()..]);
+            let latest = state.population.generation;
+            let timeline = state.history.range().unwrap_or((latest, latest));
+            // Pokolenie usunięte już z historii – pokaż najstarsze zapamiętane.
+            self.view_generation = self.view_generation.map(|g| g.clamp(timeline.0, timeline.1));
+            let viewed = self.view_generation.and_then(|g| state.history.get(g));
+            let genome_info = match state.params.representation {
+                Representation::Binary => format!(
+                    "{} bitów, {}",
+                    genome_len(&state.params.vars),
+                    state.params.encoding.label()
+                ),
+                Representation::Real => format!("{} liczb rzeczywistych", state.params.vars.len()),
+            };
+            let operators = state.params.operators_summary();
+            (
+                viewed.unwrap_or(&state.population).clone(),
+                timeline,
+                state.history.capacity(),
+                state.running,
+                state.batch_active,
+                state.run_seed,
+                genome_info,
+                operators,
+                state.stop_reason,
+                state.params.vars.iter().map(|v| v.bits).collect::<Vec<_>>(),
+            )
         };
 
-        egui::SidePanel::right("panel_populacja")
-            .default_width(340.0)
+        // Statystyki pożyczone z pamięci podręcznej na czas klatki (domknięcia
+        // niżej modyfikują inne pola `self`); wracają na miejsce na końcu `update`.
+        let stats_snapshot = std::mem::take(&mut self.stats_cache);
+
+        // -- Menu: zapis i odczyt przebiegu ----------------------------------
+        let hotkey_save = ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::S));
+        let hotkey_open = ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::O));
+        // Wczytanie zastępuje przebieg, więc nie w trakcie obliczeń ani lekcji.
+        let can_open = !ga_running && !self.ga_state.lock().unwrap().auto_active && self.lesson.is_none();
+        egui::TopBottomPanel::top("menu").show(ctx, |ui| {
+            egui::MenuBar::new().ui(ui, |ui| {
+                ui.menu_button("Plik", |ui| {
+                    let open = egui::Button::new("Otwórz przebieg…").shortcut_text("Ctrl+O");
+                    if ui.add_enabled(can_open, open).clicked() {
+                        self.open_run();
+                        ui.close();
+                    }
+                    if ui.add(egui::Button::new("Zapisz przebieg…").shortcut_text("Ctrl+S")).clicked() {
+                        self.save_run();
+                        ui.close();
+                    }
+                    ui.checkbox(&mut self.save_history, "Zapisuj historię statystyk");
+                    ui.separator();
+                    // Do analizy w pandas / R: oglądane pokolenie i statystyki całego przebiegu.
+                    let population = ui.button("Eksportuj populację…")
+                        .on_hover_text("Ranga, bity, x, f i fitness osobników oglądanego pokolenia (CSV albo JSON)");
+                    if population.clicked() {
+                        let names = self.ga_state.lock().unwrap().engine.objective.vars().to_vec();
+                        self.export_table("Eksportuj populację", "populacja", |format| {
+                            export::population(&population_snapshot, &names, format)
+                        });
+                        ui.close();
+                    }
+                    let stats = ui.button("Eksportuj statystyki…")
+                        .on_hover_text("Statystyki kolejnych pokoleń przebiegu (CSV albo JSON)");
+                    if stats.clicked() {
+                        self.export_table("Eksportuj statystyki", "statystyki", |format| {
+                            export::stats(&stats_snapshot, format)
+                        });
+                        ui.close();
+                    }
+                    let plot = ui.button("Eksportuj wykres…")
+                        .on_hover_text("Wykres funkcji z oglądanym pokoleniem, w rozmiarze jak na ekranie (SVG albo PNG)");
+                    if plot.clicked() {
+                        let marks = self.lesson.as_ref().map(Lesson::marks).unwrap_or_default();
+                        let overlay = PlotOverlay { population: Some(&population_snapshot), marks: &marks };
+                        self.export_plot(&overlay, ui.visuals().dark_mode);
+                        ui.close();
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("Skala PNG");
+                        ui.add(egui::DragValue::new(&mut self.png_scale).range(1.0..=8.0).speed(0.1).suffix("×"));
+                    });
+                });
+                ui.menu_button("Ustawienia", |ui| {
+                    ui.label("Motyw");
+                    for theme in Theme::ALL {
+                        if ui.radio_value(&mut self.theme, theme, theme.label()).clicked() {
+                            ctx.set_theme(theme.preference());
+                        }
+                    }
+                    ui.separator();
+                    // Jak wczytanie pliku – zastępuje przebieg.
+                    let restore = ui.add_enabled(can_open, egui::Button::new("Przywróć ustawienia domyślne"))
+                        .on_hover_text(
+                            "Parametry GA, funkcja celu, dziedzina, motyw, szerokość panelu i rozmiar okna \
+                             wracają do wartości domyślnych; zaczyna się nowy przebieg",
+                        );
+                    if restore.clicked() {
+                        self.restore_defaults(ctx);
+                        ui.close();
+                    }
+                    if let Some(path) = Settings::path() {
+                        let note = format!("Zapisywane przy zamykaniu w\n{}", path.display());
+                        ui.label(egui::RichText::new(note).small().weak());
+                    }
+                });
+                match &self.file_status {
+                    Some(Ok(msg))  => { ui.label(msg); }
+                    Some(Err(err)) => { ui.label(egui::RichText::new(err).color(Color32::from_rgb(220, 60, 60))); }
+                    None => {}
+                }
+            });
+        });
+        if hotkey_save {
+            self.save_run();
+        }
+        if hotkey_open && can_open {
+            self.open_run();
+        }
+
+        let side_panel = egui::SidePanel::right("panel_populacja")
+            .default_width(self.panel_width)
             .resizable(true)
             .show(ctx, |ui| {
                 // -- Prawa kolumna: podgląd populacji -----------------------
                 ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
                     let generation = population_snapshot.generation;
                     let best = population_snapshot.best()
-                        .map(|c| format!("x={:.4}  f={:.4}", c.x, c.fitness))
+                        .map(|c| format!("x={}  f={:.4}  fit={:.4}", c.xs_str().trim(), c.value, c.fitness))
                         .unwrap_or_default();
 
-                    ui.label(
-                        egui::RichText::new(format!("Pokolenie #{generation}"))
-                            .strong()
-                    );
+                    let title = if generation < timeline.1 {
+                        format!("Pokolenie #{generation}  (podgląd, najnowsze #{})", timeline.1)
+                    } else {
+                        format!("Pokolenie #{generation}")
+                    };
+                    ui.label(egui::RichText::new(title).strong());
                     ui.label(
                         egui::RichText::new(format!("Najlepszy: {best}"))
                             .strong()
                             .color(Color32::from_rgb(220, 140, 0))
                     );
+                    ui.label(format!("Ziarno: {run_seed}   Genom: {genome_info}"));
+                    ui.label(egui::RichText::new(operators).small());
+                    if let Some(reason) = stop_reason {
+                        ui.label(
+                            egui::RichText::new(format!("⏹ Zatrzymano: {reason}"))
+                                .color(Color32::from_rgb(200, 60, 60))
+                        );
+                    }
                     ui.add_space(4.0);
                     ui.separator();
 
+                    // -- Różnorodność oglądanego pokolenia --------------------
+                    if let Some(s) = stats_snapshot.iter().rfind(|s| s.generation == generation) {
+                        let hamming = if s.hamming.is_nan() { "—".to_string() } else { format!("{:.3}", s.hamming) };
+                        ui.label(format!(
+                            "Różnorodność: Hamming {hamming}   rozrzut x {:.3}   unikalne {}/{}",
+                            s.spread,
+                            s.unique,
+                            population_snapshot.chromosomes.len()
+                        ));
+                    }
+                    let freqs = population_snapshot.allele_frequencies();
+                    if !freqs.is_empty() {
+                        ui.add(AlleleStrip { freqs: &freqs, bits: &var_bits });
+                        ui.add_space(2.0);
+                    }
+                    ui.separator();
+
+                    // Archiwum rodowodu obejmuje tylko ostatnie pokolenia – dla
+                    // starszych (oglądanych na osi czasu) rodowodu nie ma.
+                    let lineage_available = generation + Genealogy::DEFAULT_DEPTH >= timeline.1;
+                    if !lineage_available {
+                        ui.label(egui::RichText::new(format!(
+                            "Rodowód niedostępny – archiwum obejmuje ostatnie {} pokoleń.",
+                            Genealogy::DEFAULT_DEPTH
+                        )).small().italics());
+                    }
+
                     // Lista chromosomów – przewijalna, z możliwością zaznaczenia wiersza.
                     egui::ScrollArea::vertical()
                         .id_salt("pop_list")
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
                             for (i, chrom) in population_snapshot.chromosomes.iter().enumerate() {
-                                let text = format!("{:>2}. {}", i + 1, chrom.display_str());
-                                let is_selected = self.selected_idx == Some(i);
+                                // Gwiazdka: osobnik przeżył z wcześniejszego pokolenia (np. elita).
+                                let survivor = if chrom.origin.birth < generation { '*' } else { ' ' };
+                                let text = format!("{:>2}.{survivor} {}", i + 1, chrom.display_str());
+                                let id = chrom.origin.id;
+                                let is_selected = self.selected_id == Some(id);
                                 let color = if i == 0 {
                                     Color32::from_rgb(220, 140, 0)   // najlepszy – pomarańczowy
                                 } else {
@@ -658,13 +1657,19 @@ impl eframe::App for MyApp {
                                     is_selected,
                                     egui::RichText::new(text).monospace().color(color).size(11.0),
                                 ).frame(false);
-                                if ui.add(label).clicked() {
-                                    self.selected_idx = if is_selected { None } else { Some(i) };
+                                let hover = format!(
+                                    "osobnik #{id}, pokolenie {}: {}",
+                                    chrom.origin.birth,
+                                    chrom.origin.operators_str()
+                                );
+                                if ui.add(label).on_hover_text(hover).clicked() && lineage_available {
+                                    self.selected_id = if is_selected { None } else { Some(id) };
                                 }
                             }
                         });
                 });
             });
+        self.panel_width = side_panel.response.rect.width();
 
         // Sprawdź skróty klawiszowe (niezależnie od fokusa przycisku).
         let hotkey_calc  = ctx.input_mut(|i| i.consume_key(egui::Modifiers::ALT, egui::Key::C));
@@ -677,15 +1682,123 @@ impl eframe::App for MyApp {
         let auto_active = self.ga_state.lock().unwrap().auto_active;
 
         egui::CentralPanel::default().show(ctx, |ui| {
+            let manual_enabled = !ga_running && !auto_active && self.lesson.is_none();
+
+            // -- Góra: pole funkcji celu -------------------------------------
+            // Wyrażenie jest parsowane przy każdej zmianie (błąd widać od razu),
+            // ale do GA trafia dopiero po Enter / "Zastosuj" – wtedy przebieg
+            // startuje od nowa, bo stara populacja była oceniana inną funkcją.
+            let mut apply = false;
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("f =").monospace());
+                let apply_width = 170.0;
+                let edit = ui.add(
+                    egui::TextEdit::singleline(&mut self.expr_text)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width((ui.available_width() - apply_width).max(40.0)),
+                );
+                if edit.changed() {
+                    self.expr_error = Objective::parse(&self.expr_text).err();
+                }
+                if edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    apply = true;
+                }
+                let can_apply = manual_enabled && self.expr_error.is_none();
+                if ui.add_enabled(can_apply, egui::Button::new("Zastosuj")).clicked() {
+                    apply = true;
+                }
+                ui.add_enabled_ui(manual_enabled, |ui| {
+                    ui.menu_button("Przykłady", |ui| {
+                        for (name, src) in EXAMPLES {
+                            if ui.button(*name).clicked() {
+                                self.expr_text = src.to_string();
+                                self.expr_error = None;
+                                apply = true;
+                                ui.close();
+                            }
+                        }
+                    });
+                });
+            });
+            if let Some(err) = &self.expr_error {
+                // Źródło + daszek pod miejscem błędu (czcionka stałej szerokości).
+                let caret = format!("{}^ {}", " ".repeat(err.pos), err.msg);
+                ui.label(
+                    egui::RichText::new(format!("{}\n{}", self.expr_text, caret))
+                        .monospace()
+                        .color(Color32::from_rgb(220, 60, 60)),
+                );
+            } else if apply && manual_enabled && let Ok(func) = Objective::parse(&self.expr_text) {
+                // Liczba zmiennych wynika z wyrażenia – dziedziny nowych
+                // zmiennych kopiujemy z ostatniej istniejącej.
+                let mut state = self.ga_state.lock().unwrap();
+                state.params.resize_vars(func.dims());
+                self.plot.func = func.clone();
+                self.plot.set_domain(&state.params.vars);
+                state.engine.objective = func;
+                state.restart();
+                self.selected_id = None;
+                self.view_generation = None;
+            }
+            ui.add_space(4.0);
+
             // -- Środek: wykres + przyciski ----------------------------------
             // Zarezerwuj pasek na przyciski na dole, reszta idzie na wykres.
             let btn_height = 28.0;
             let spacing   = 6.0;
-            let plot_height = (ui.available_height() - btn_height - spacing).max(2.0);
+            let timeline_height = 20.0;
+            let mut plot_height = (ui.available_height() - btn_height - timeline_height - 2.0 * spacing).max(2.0);
+
+            // Pod wykresem funkcji (o ile włączony) wykres historii – ok. 1/3 wysokości.
+            let show_chart = self.history_chart != HistoryChart::Hidden;
+            let chart_height = if show_chart { (plot_height * 0.35).round() } else { 0.0 };
+            if show_chart {
+                plot_height = (plot_height - chart_height - spacing).max(2.0);
+            }
 
             // Wykres zajmuje górną część.
             let plot_size = Vec2::new(ui.available_width(), plot_height);
-            ui.add_sized(plot_size, FunctionPlotWidget::new(&self.plot, Some(&population_snapshot)));
+            let marks = self.lesson.as_ref().map(Lesson::marks).unwrap_or_default();
+            self.plot_size = ui.add_sized(
+                plot_size,
+                FunctionPlotWidget::new(&self.plot, Some(&population_snapshot)).with_marks(&marks),
+            ).rect.size();
+
+            // -- Oś czasu: przewijanie zapamiętanych pokoleń -----------------
+            // Suwak na ostatnim pokoleniu = podgląd na żywo (nowe pokolenia
+            // pojawiają się od razu); cofnięty zostaje na wybranym pokoleniu.
+            ui.add_space(spacing);
+            ui.allocate_ui(Vec2::new(ui.available_width(), timeline_height), |ui| {
+                ui.horizontal(|ui| {
+                    let (first, last) = timeline;
+                    let mut generation = self.view_generation.unwrap_or(last);
+                    ui.label("Pokolenie");
+                    ui.spacing_mut().slider_width = (ui.available_width() - 160.0).max(40.0);
+                    let slider = ui.add_enabled(
+                        first < last && self.lesson.is_none(),
+                        egui::Slider::new(&mut generation, first..=last),
+                    ).on_hover_text(format!(
+                        "Przewijanie przebiegu (zapamiętanych jest najwyżej {history_capacity} ostatnich pokoleń)"
+                    ));
+                    if slider.changed() {
+                        self.view_generation = (generation < last).then_some(generation);
+                    }
+                    if ui.add_enabled(self.view_generation.is_some(), egui::Button::new("Najnowsze")).clicked() {
+                        self.view_generation = None;
+                    }
+                });
+            });
+
+            if show_chart {
+                ui.add_space(spacing);
+                let chart_size = Vec2::new(ui.available_width(), chart_height);
+                let pop_size = population_snapshot.chromosomes.len();
+                ui.add_sized(
+                    chart_size,
+                    HistoryChartWidget::new(&stats_snapshot, self.history_chart, pop_size)
+                        .with_selected(self.view_generation),
+                );
+            }
 
             ui.add_space(spacing);
 
@@ -712,28 +1825,55 @@ impl eframe::App for MyApp {
                 // Lewy margines wyrównujący pasek do centrum.
                 ui.add_space(left_margin);
 
-                let manual_enabled = !ga_running && !auto_active;
-
                 let btn_calc = ui.add_enabled(
                     manual_enabled,
                     egui::Button::new("Następna generacja").shortcut_text("Alt+C"),
                 );
 
                 if btn_calc.clicked() || (manual_enabled && hotkey_calc) {
-                    self.spawn_ga_step();
+                    self.spawn_ga_steps(1);
                 }
 
+                // Tryb nauki: to samo pokolenie rozpisane na kroki.
+                let btn_lesson = ui.add_enabled(manual_enabled, egui::Button::new("Krok po kroku"))
+                    .on_hover_text("Pokazuje kolejne etapy obliczania następnej generacji");
+                if btn_lesson.clicked() {
+                    self.start_lesson();
+                }
+
+                // Seria N pokoleń w jednym wątku – okno odświeża się w tym
+                // czasie co REPAINT_INTERVAL, a nie po każdym pokoleniu.
+                // Serię kończą też warunki zatrzymania (jak tryb Auto).
+                // W trakcie serii "Uruchom" zamienia się w "Przerwij".
+                if batch_active {
+                    let stop = ui.button("⏹ Przerwij").on_hover_text("Przerywa serię po bieżącym pokoleniu");
+                    if stop.clicked() {
+                        self.ga_state.lock().unwrap().batch_active = false;
+                    }
+                } else {
+                    let run = ui.add_enabled(manual_enabled, egui::Button::new("Uruchom"))
+                        .on_hover_text("Oblicza podaną liczbę pokoleń (mniej, gdy zadziała warunek zatrzymania)");
+                    if run.clicked() {
+                        self.spawn_batch(self.batch_generations);
+                    }
+                }
+                ui.add_enabled(
+                    manual_enabled,
+                    egui::DragValue::new(&mut self.batch_generations)
+                        .speed(1.0)
+                        .range(1..=100_000)
+                        .suffix(" pok."),
+                );
+
                 let btn_reset = ui.add_enabled(
                     manual_enabled,
                     egui::Button::new("Reset").shortcut_text("Alt+R"),
                 );
 
                 if btn_reset.clicked() || (manual_enabled && hotkey_reset) {
-                    let pop_size = self.ga_state.lock().unwrap().params.pop_size;
-                    let pop = Population::random(pop_size, -10.0, 10.0, FunctionPlot::target);
-                    let mut state = self.ga_state.lock().unwrap();
-                    state.population = pop;
-                    self.selected_idx = None;
+                    self.ga_state.lock().unwrap().restart();
+                    self.selected_id = None;
+                    self.view_generation = None;
                 }
 
                 // Przycisk Auto – toggle, zmienia kolor gdy aktywny.
@@ -744,15 +1884,19 @@ impl eframe::App for MyApp {
                     ui.visuals().widgets.inactive.fg_stroke.color
                 };
 
-                let btn_auto = ui.add(
+                let btn_auto = ui.add_enabled(
+                    self.lesson.is_none(),
                     egui::Button::new(
                         egui::RichText::new(auto_label).color(auto_color)
                     ).shortcut_text("Alt+A"),
                 );
 
-                if btn_auto.clicked() || hotkey_auto {
+                if btn_auto.clicked() || (self.lesson.is_none() && hotkey_auto) {
                     let mut state = self.ga_state.lock().unwrap();
                     state.auto_active = !state.auto_active;
+                    if state.auto_active {
+                        state.start_stop_monitor();
+                    }
                     // Blokada: uruchamiaj wątek tylko jeśli nie działa
                     if state.auto_active && !state.auto_thread_running {
                         state.auto_thread_running = true;
@@ -766,11 +1910,35 @@ impl eframe::App for MyApp {
                     }
                 }
 
+                // Tempo trybu Auto: 0 ms = najszybciej (okno i tak odświeża
+                // się najwyżej co REPAINT_INTERVAL), do kilku sekund na pokolenie.
+                let tempo = ui.add(
+                    egui::Slider::new(&mut self.auto_delay_ms, 0..=5000)
+                        .logarithmic(true)
+                        .custom_formatter(|ms, _| {
+                            if ms < 1.0 { "max".to_string() } else { format!("{ms:.0} ms") }
+                        }),
+                ).on_hover_text("Przerwa między pokoleniami w trybie Auto");
+                if tempo.changed() {
+                    self.ga_state.lock().unwrap().auto_delay = Duration::from_millis(self.auto_delay_ms);
+                }
+
                 ui.add_space(18.0);
                 let btn_opcje = ui.add(egui::Button::new("Opcje").shortcut_text("Alt+O"));
+                // Izolinie dotyczą tylko mapy funkcji dwóch zmiennych.
+                ui.add_enabled(self.plot.is_map(), egui::Checkbox::new(&mut self.plot.contours, "Izolinie"));
+                egui::ComboBox::from_id_salt("history_chart")
+                    .selected_text(self.history_chart.label())
+                    .show_ui(ui, |ui| {
+                        for kind in HistoryChart::ALL {
+                            ui.selectable_value(&mut self.history_chart, kind, kind.label());
+                        }
+                    })
+                    .response
+                    .on_hover_text("Wykres historii przebiegu pod wykresem funkcji");
                 if btn_opcje.clicked() || ctx.input(|i| i.key_pressed(egui::Key::O) && i.modifiers.alt) {
-                    let params = self.ga_state.lock().unwrap().params.clone();
-                    self.options_window.open_with(&params);
+                    let state = self.ga_state.lock().unwrap();
+                    self.options_window.open_with(&state.params, state.engine.objective.vars());
                 }
             });
 
@@ -783,20 +1951,235 @@ impl eframe::App for MyApp {
             }
         });
 
+        self.show_lineage(ctx);
+
+        match self.lesson.as_mut().map(|lesson| lesson.show(ctx)) {
+            Some(LessonAction::Finish) => {
+                let lesson = self.lesson.take().unwrap();
+                let mut state = self.ga_state.lock().unwrap();
+                // Lekcja policzona przed restartem dotyczy starego przebiegu.
+                if state.run_epoch == lesson.epoch {
+                    let mut population = lesson.result;
+                    let mode = state.params.fitness;
+                    population.rescore(&mode);
+                    state.advance(population, lesson.rng);
+                }
+            }
+            Some(LessonAction::Cancel) => self.lesson = None,
+            Some(LessonAction::None) | None => {}
+        }
+
         // Okno opcji – delegujemy całą logikę do OptionsWindow::show()
         if let Some(params) = self.options_window.show(ctx) {
-            self.ga_state.lock().unwrap().params = params;
+            let mut state = self.ga_state.lock().unwrap();
+            let restart = state.params.domain_changed(&params);
+            self.plot.set_domain(&params.vars);
+            // Zmiana kierunku / przekształcenia nie wymaga nowego przebiegu –
+            // wystarczy przeliczyć fitness populacji i historii przebiegu.
+            if state.params.fitness != params.fitness {
+                state.set_fitness_mode(params.fitness);
+            }
+            state.params = params;
+            // Inna liczebność populacji zmienia rozmiar pokolenia w historii.
+            let capacity = History::capacity_for(History::DEFAULT_BUDGET, &state.params);
+            state.history.set_capacity(capacity);
+            // Nowa dziedzina = nowy przebieg, żeby wykres, dekodowanie
+            // i populacja zawsze dotyczyły tego samego przedziału.
+            if restart {
+                state.restart();
+                self.selected_id = None;
+                self.lesson = None;
+                self.view_generation = None;
+            }
         }
+
+        self.stats_cache = stats_snapshot;
     }
 }
 
 impl MyApp {
-    /// Uruchamia długo żyjący wątek obsługujący auto-calculate.
+    /// Liczy następne pokolenie z zapisem przebiegu ([`Engine::step_explained`])
+    /// na kopii populacji i generatora – GA zmieni się dopiero po zakończeniu lekcji.
+    fn start_lesson(&mut self) {
+        // Lekcja dotyczy najnowszego pokolenia – wróć do niego na osi czasu.
+        self.view_generation = None;
+        let state = self.ga_state.lock().unwrap();
+        let mut population = state.population.clone();
+        let mut rng = state.rng.clone();
+        let trace = state.engine.step_explained(&mut population, &state.params, &mut rng);
+        self.lesson = Some(Lesson::new(
+            state.population.clone(),
+            population,
+            rng,
+            trace,
+            state.params.clone(),
+            state.run_epoch,
+        ));
+    }
+
+    /// Zapisuje bieżący przebieg do pliku wybranego w oknie dialogowym.
+    fn save_run(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Zapisz przebieg")
+            .add_filter("Przebieg GA", &[document::EXTENSION])
+            .set_file_name(format!("przebieg.{}", document::EXTENSION))
+            .save_file()
+        else {
+            return;
+        };
+        // Kopia dokumentu pod blokadą – sam zapis na dysk już bez niej.
+        let doc = self.ga_state.lock().unwrap().document(self.save_history);
+        self.file_status = Some(doc.save(&path).map(|()| format!("Zapisano {}", path.display())));
+    }
+
+    /// Wczytuje przebieg z pliku wybranego w oknie dialogowym i kontynuuje go.
+    fn open_run(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Otwórz przebieg")
+            .add_filter("Przebieg GA", &[document::EXTENSION])
+            .pick_file()
+        else {
+            return;
+        };
+        let loaded = RunDocument::load(&path).and_then(|doc| {
+            let objective = doc.objective()?;
+            let (source, vars) = (doc.objective.clone(), doc.params.vars.clone());
+            self.ga_state.lock().unwrap().load(doc, objective.clone())?;
+            self.expr_text = source;
+            self.expr_error = None;
+            self.plot.func = objective;
+            self.plot.set_domain(&vars);
+            self.selected_id = None;
+            self.lesson = None;
+            self.view_generation = None;
+            Ok(())
+        });
+        self.file_status = Some(loaded.map(|()| format!("Wczytano {}", path.display())));
+    }
+
+    /// Zapisuje tabelę eksportu do pliku wybranego w oknie dialogowym;
+    /// format (CSV albo JSON) wynika z rozszerzenia pliku.
+    fn export_table(&mut self, title: &str, name: &str, table: impl Fn(ExportFormat) -> String) {
+        let mut dialog = rfd::FileDialog::new()
+            .set_title(title)
+            .set_file_name(format!("{name}.{}", ExportFormat::Csv.extension()));
+        for format in ExportFormat::ALL {
+            dialog = dialog.add_filter(format.label(), &[format.extension()]);
+        }
+        let Some(path) = dialog.save_file() else { return };
+        let saved = ExportFormat::from_path(&path).and_then(|format| export::save(&path, &table(format)));
+        self.file_status = Some(saved.map(|()| format!("Wyeksportowano {}", path.display())));
+    }
+
+    /// Bieżące ustawienia do zapisania w pliku konfiguracji.
+    fn settings(&self) -> Settings {
+        let state = self.ga_state.lock().unwrap();
+        Settings {
+            objective:   state.engine.objective.label().to_string(),
+            theme:       self.theme,
+            panel_width: self.panel_width,
+            window_size: self.window_size.into(),
+            window_pos:  self.window_pos.map(Into::into),
+            params:      state.params.clone(),
+        }
+    }
+
+    /// Przywraca ustawienia domyślne (parametry GA, funkcję celu z dziedziną,
+    /// motyw, szerokość panelu i rozmiar okna) i zaczyna nowy przebieg.
+    /// W odróżnieniu od "Reset" w oknie opcji dotyczy całego programu.
+    fn restore_defaults(&mut self, ctx: &egui::Context) {
+        let defaults = Settings::default();
+        {
+            let mut state = self.ga_state.lock().unwrap();
+            state.params = defaults.params.clone();
+            state.engine.objective = defaults.objective();
+            state.restart();
+        }
+        self.plot.func = defaults.objective();
+        self.plot.set_domain(&defaults.params.vars);
+        self.expr_text = defaults.objective.clone();
+        self.expr_error = None;
+        self.options_window = OptionsWindow::new(&defaults.params);
+        self.selected_id = None;
+        self.lesson = None;
+        self.view_generation = None;
+
+        self.theme = defaults.theme;
+        ctx.set_theme(defaults.theme.preference());
+        // Panel zapamiętuje szerokość w pamięci egui – bez niej wraca do domyślnej.
+        ctx.data_mut(|d| d.remove::<egui::containers::panel::PanelState>(egui::Id::new("panel_populacja")));
+        self.panel_width = defaults.panel_width;
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(Vec2::from(defaults.window_size)));
+        self.file_status = Some(Ok("Przywrócono ustawienia domyślne".into()));
+    }
+
+    /// Zapisuje wykres funkcji do pliku SVG albo PNG wybranego w oknie
+    /// dialogowym; PNG ma `png_scale` razy więcej pikseli niż wykres na ekranie.
+    fn export_plot(&mut self, overlay: &PlotOverlay, dark_mode: bool) {
+        let mut dialog = rfd::FileDialog::new()
+            .set_title("Eksportuj wykres")
+            .set_file_name(format!("wykres.{}", PlotFormat::Png.extension()));
+        for format in PlotFormat::ALL {
+            dialog = dialog.add_filter(format.label(), &[format.extension()]);
+        }
+        let Some(path) = dialog.save_file() else { return };
+        let colors = if dark_mode {
+            PlotColors::default_dark()
+        } else {
+            PlotColors::default_light()
+        };
+        let svg = self.plot.to_svg(self.plot_size, &colors, overlay);
+        let saved = PlotFormat::from_path(&path)
+            .and_then(|format| render::save_plot(&path, format, &svg, self.png_scale));
+        self.file_status = Some(saved.map(|()| format!("Wyeksportowano {}", path.display())));
+    }
+
+    /// Okno rodowodu osobnika zaznaczonego na liście populacji. Zamknięcie
+    /// okna odznacza osobnika.
+    fn show_lineage(&mut self, ctx: &egui::Context) {
+        let Some(id) = self.selected_id else { return };
+        // Przodkowie są kopiowani raz, po zaznaczeniu osobnika – okno rysuje
+        // się bez blokady stanu GA (zapisani przodkowie już się nie zmieniają).
+        {
+            let state = self.ga_state.lock().unwrap();
+            if !matches!(&self.lineage, Some((epoch, selected, _)) if (*epoch, *selected) == (state.run_epoch, id)) {
+                self.lineage = Some((state.run_epoch, id, state.genealogy.ancestry(id)));
+            }
+        }
+        let Some((_, _, genealogy)) = &self.lineage else { return };
+        let mut open = true;
+        egui::Window::new(format!("Rodowód osobnika #{id}"))
+            .open(&mut open)
+            .default_width(460.0)
+            .default_height(360.0)
+            .show(ctx, |ui| match genealogy.get(id) {
+                Some(chrom) => {
+                    ui.label(
+                        egui::RichText::new("| – punkt cięcia krzyżowania, czerwony bit – mutacja").small(),
+                    );
+                    egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                        lineage_node(ui, genealogy, chrom, 0, "lineage".to_string());
+                    });
+                }
+                None => {
+                    ui.label(format!(
+                        "Osobnik wypadł z archiwum (przechowywane są ostatnie {} pokoleń).",
+                        Genealogy::DEFAULT_DEPTH
+                    ));
+                }
+            });
+        if !open {
+            self.selected_id = None;
+            self.lineage = None;
+        }
+    }
+
+    /// Uruchamia wątek obsługujący auto-calculate.
     ///
-    /// Wątek działa w nieskończoność (aż do zamknięcia programu). Co sekundę
-    /// sprawdza flagę `auto_active` w mutexie:
-    ///   - jeśli true  → odpala krok GA (jeśli poprzedni już się skończył)
-    ///   - jeśli false → śpi dalej bez nic nie robiąc
+    /// Wątek liczy pokolenie, śpi `auto_delay` i sprawdza flagę `auto_active`
+    /// w mutexie:
+    ///   - jeśli true  → odpala kolejny krok GA (jeśli poprzedni już się skończył)
+    ///   - jeśli false → zeruje `auto_thread_running` i kończy się
     ///
     /// Dzięki temu GUI nie musi nic pollować – wystarczy ustawić flagę.
     fn spawn_auto_thread(state_arc: Arc<Mutex<GaState>>, ctx: Option<egui::Context>) {
@@ -808,11 +2191,14 @@ impl MyApp {
                 };
 
                 if should_run {
-                    Self::calculate(Arc::clone(&state_arc), &ctx);
+                    Self::calculate(Arc::clone(&state_arc), &ctx, 1);
                 }
 
                 // Jeśli auto zostało wyłączone, kończymy wątek.
-                let still_active = state_arc.lock().unwrap().auto_active;
+                let (still_active, delay) = {
+                    let state = state_arc.lock().unwrap();
+                    (state.auto_active, state.auto_delay)
+                };
                 if !still_active {
                     // Wyzeruj flagę auto_thread_running po zakończeniu wątku
                     let mut state = state_arc.lock().unwrap();
@@ -820,13 +2206,28 @@ impl MyApp {
                     break;
                 }
 
-                std::thread::sleep(std::time::Duration::from_secs(1));
+                if delay.is_zero() {
+                    std::thread::yield_now();
+                } else {
+                    std::thread::sleep(delay);
+                }
             }
         });
     }
 
-    /// Odpala obliczenie nowej generacji w osobnym wątku.
-    fn spawn_ga_step(&self) {
+    /// Odpala serię `generations` pokoleń przerywaną przyciskiem "Przerwij"
+    /// i warunkami zatrzymania.
+    fn spawn_batch(&self, generations: usize) {
+        {
+            let mut state = self.ga_state.lock().unwrap();
+            state.batch_active = true;
+            state.start_stop_monitor();
+        }
+        self.spawn_ga_steps(generations);
+    }
+
+    /// Odpala obliczenie `generations` kolejnych pokoleń w osobnym wątku.
+    fn spawn_ga_steps(&self, generations: usize) {
         let state_arc = Arc::clone(&self.ga_state);
         let ctx       = self.ctx.clone();
 
@@ -837,129 +2238,219 @@ impl MyApp {
         }
 
         std::thread::spawn(move || {
-            Self::calculate(state_arc, &ctx);
+            Self::calculate(state_arc, &ctx, generations);
         });
     }
 
-    fn calculate(state_arc: Arc<Mutex<GaState>>, ctx: &Option<egui::Context>) {
-        // Pobierz aktualną populację, numer pokolenia i aktualne parametry GA.
-        let (old_pop, new_gen, pop_size, tournament_k, crossover_prob, mutation_prob) = {
+    /// Oblicza `generations` pokoleń, zapisując stan po każdym z nich
+    /// (panel i wykres pokazują postęp serii).
+    fn calculate(state_arc: Arc<Mutex<GaState>>, ctx: &Option<egui::Context>, generations: usize) {
+        // Pobierz aktualną populację, silnik, parametry GA i generator przebiegu.
+        // Generator wraca do stanu po obliczeniu, żeby strumień losowań był ciągły.
+        let (mut population, engine, params, mut rng, epoch, batch) = {
             let state = state_arc.lock().unwrap();
             (
                 state.population.clone(),
-                state.population.generation + 1,
-                state.params.pop_size,
-                state.params.tournament_k,
-                state.params.crossover_prob,
-                state.params.mutation_prob,
+                state.engine.clone(),
+                state.params.clone(),
+                state.rng.clone(),
+                state.run_epoch,
+                state.batch_active,
             )
         };
 
-        // Seed oparty na czasie, żeby każde pokolenie było naprawdę losowe.
-        let seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.subsec_nanos() as u64)
-            .unwrap_or(new_gen as u64)
-            .wrapping_mul(new_gen as u64 + 1)
-            .wrapping_add(0xdeadbeef);
-        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
-
-        let parents = &old_pop.chromosomes;
-
-        // -- Selekcja turniejowa ---------------------------------------------
-        // Losujemy K osobników, wygrywa ten z najwyższym fitness.
-        // Wyobraź sobie turniej: losowo wybierasz K zawodników ze starej
-        // populacji i przepuszczasz najlepszego dalej. Powtarzasz tyle razy,
-        // ile potrzebujesz rodziców.
-        let tournament = |rng: &mut rand::rngs::StdRng| -> &Chromosome {
-            let mut best_idx = rng.gen_range(0..parents.len());
-            for _ in 1..tournament_k {
-                let idx = rng.gen_range(0..parents.len());
-                if parents[idx].fitness > parents[best_idx].fitness {
-                    best_idx = idx;
-                }
-            }
-            &parents[best_idx]
-        };
+        for _ in 0..generations {
+            engine.step(&mut population, &params, &mut rng);
 
-        // -- Krzyżowanie jednopunktowe ---------------------------------------
-        // Wybieramy losowy punkt cięcia i sklejamy lewy kawałek jednego
-        // rodzica z prawym kawałkiem drugiego.
-        // Np. rodzic A: 1101|0011  rodzic B: 0010|1100
-        //     dziecko:  1101|1100
-        let crossover = |a: &Chromosome, b: &Chromosome, rng: &mut rand::rngs::StdRng| -> [bool; BITS] {
-            let mut genes = a.genes;
-            if rng.gen_bool(crossover_prob) {
-                // punkt cięcia: 1..BITS-1
-                let point = rng.gen_range(1..BITS);
-                for i in point..BITS {
-                    genes[i] = b.genes[i];
-                }
+            // Zapisz wynik. Jeśli w międzyczasie przebieg został
+            // zrestartowany, wynik dotyczy starego i przepada (razem z resztą serii).
+            let mut state = state_arc.lock().unwrap();
+            if state.run_epoch != epoch {
+                break;
             }
-            genes
-        };
-
-        // -- Mutacja bitowa --------------------------------------------------
-        // Każdy bit może się losowo odwrócić z prawdopodobieństwem MUTATION_PROB.
-        // Wyobraź sobie kosmiczne promieniowanie, które z rzadka przełącza
-        // jeden bit w DNA.
-        let mutate = |genes: &mut [bool; BITS], rng: &mut rand::rngs::StdRng| {
-            for bit in genes.iter_mut() {
-                if rng.gen_bool(mutation_prob) {
-                    *bit = !*bit;
+            state.advance(population.clone(), rng.clone());
+            // Warunki zatrzymania dotyczą serii i trybu Auto (nie pojedynczego
+            // kroku) – wyłączenie flagi kończy serię albo pętlę wątku auto.
+            if state.auto_active || state.batch_active {
+                let GaState { stop_monitor, population, params, .. } = &mut *state;
+                let reason = stop_monitor.observe(population, &params.stop, &params.fitness, &params.vars);
+                if reason.is_some() {
+                    state.auto_active = false;
+                    state.batch_active = false;
+                    state.stop_reason = reason;
                 }
             }
-        };
-
-        // -- Elityzm: najlepszy osobnik przechodzi bez zmian -----------------
-        let mut new_chromosomes: Vec<Chromosome> = Vec::with_capacity(pop_size);
-        if let Some(elite) = old_pop.best() {
-            new_chromosomes.push(elite.clone());
-        }
+            let cancelled = batch && !state.batch_active;
+            drop(state);
 
-        // -- Wypełnij resztę populacji dziećmi -------------------------------
-        while new_chromosomes.len() < pop_size {
-            let parent_a = tournament(&mut rng);
-            let parent_b = tournament(&mut rng);
-
-            let mut genes = crossover(parent_a, parent_b, &mut rng);
-            mutate(&mut genes, &mut rng);
-
-            let x = Chromosome::decode(&genes, -10.0, 10.0);
-            let mut child = Chromosome { genes, fitness: 0.0, x };
-            child.evaluate(FunctionPlot::target);
-            new_chromosomes.push(child);
+            if let Some(ctx) = ctx {
+                ctx.request_repaint_after(REPAINT_INTERVAL);
+            }
+            if cancelled {
+                break;
+            }
         }
 
-        // Sortuj malejąco po fitness – najlepszy na górze.
-        new_chromosomes.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
-
-        let new_population = Population { chromosomes: new_chromosomes, generation: new_gen };
-
-        // Zapisz wynik i zdejmij flagę "running".
+        // Zdejmij flagi "running" i serii, i odśwież okno z wynikiem ostatniego pokolenia
+        // (w trybie Auto "najszybciej" to też najwyżej raz na REPAINT_INTERVAL).
         {
             let mut state = state_arc.lock().unwrap();
-            state.population = new_population;
             state.running = false;
+            state.batch_active = false;
         }
-
         if let Some(ctx) = ctx {
-            ctx.request_repaint();
+            ctx.request_repaint_after(REPAINT_INTERVAL);
         }
     }
 }
 
-fn main() {
-    let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1000.0, 580.0])
-            .with_resizable(true),
-        ..Default::default()
+/// Jeden węzeł drzewa przodków: osobnik i (po rozwinięciu) jego rodzice.
+fn lineage_node(ui: &mut Ui, genealogy: &Genealogy, chrom: &Chromosome, depth: usize, path: String) {
+    let origin = &chrom.origin;
+    let header = format!(
+        "#{}  pok. {}  f={:.4}  x={}",
+        origin.id,
+        origin.birth,
+        chrom.value,
+        chrom.xs_str().trim()
+    );
+    let body = |ui: &mut Ui| {
+        if !chrom.genes.is_empty() {
+            ui.label(genes_job(chrom, ui.visuals().text_color()));
+        }
+        ui.label(egui::RichText::new(origin.operators_str()).small());
     };
+    if origin.parents.is_none() {
+        ui.label(header);
+        ui.indent(path, body);
+        return;
+    }
+    egui::CollapsingHeader::new(header)
+        .id_salt(&path)
+        .default_open(depth < 2)
+        .show(ui, |ui| {
+            body(ui);
+            let parents = genealogy.parents(origin.id);
+            if parents.is_empty() {
+                ui.label(egui::RichText::new("rodzice starsi niż archiwum").small().italics());
+            }
+            for (k, parent) in parents.into_iter().enumerate() {
+                lineage_node(ui, genealogy, parent, depth + 1, format!("{path}/{k}"));
+            }
+        });
+}
+
+/// Bity genomu z zaznaczonymi punktami cięcia i zmutowanymi bitami.
+fn genes_job(chrom: &Chromosome, color: Color32) -> egui::text::LayoutJob {
+    let font = FontId::monospace(11.0);
+    let plain = egui::TextFormat::simple(font.clone(), color);
+    let mutated = egui::TextFormat::simple(font.clone(), Color32::from_rgb(220, 60, 60));
+    let cut = egui::TextFormat::simple(font, Color32::from_rgb(220, 140, 0));
+    let cuts = chrom.origin.crossover.as_deref().unwrap_or_default();
+
+    let mut job = egui::text::LayoutJob::default();
+    for (i, &bit) in chrom.genes.iter().enumerate() {
+        if i > 0 && cuts.contains(&i) {
+            job.append("|", 0.0, cut.clone());
+        }
+        let format = if chrom.origin.mutations.contains(&i) { &mutated } else { &plain };
+        job.append(if bit { "1" } else { "0" }, 0.0, format.clone());
+    }
+    job
+}
+
+fn main() {
+    // `genetictool2 run ...` – tryb wsadowy bez okna.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("run") {
+        std::process::exit(cli::run(&args[1..]));
+    }
+
+    // Ustawienia z poprzedniej sesji; uszkodzony plik nie blokuje startu.
+    let settings = Settings::load().unwrap_or_else(|e| {
+        eprintln!("ostrzeżenie: {e} – używam ustawień domyślnych");
+        Settings::default()
+    });
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(settings.window_size)
+        .with_resizable(true);
+    if let Some(pos) = settings.window_pos {
+        viewport = viewport.with_position(pos);
+    }
+    let options = eframe::NativeOptions { viewport, ..Default::default() };
 
     eframe::run_native(
         "GeneticTool - Demo",
         options,
-        Box::new(|_cc| Ok(Box::new(MyApp::default()))),
+        Box::new(move |cc| {
+            cc.egui_ctx.set_theme(settings.theme.preference());
+            Ok(Box::new(MyApp::new(&settings)))
+        }),
     ).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_are_multiples_of_step_within_range() {
+        assert_eq!(ticks_for(-1.0, 1.0, 0.5), [-1.0, -0.5, 0.0, 0.5, 1.0]);
+        assert_eq!(ticks_for(0.1, 0.9, 0.5), [0.5]);
+        assert!(ticks_for(1.0, 0.0, 0.5).is_empty());
+        assert!(ticks_for(0.0, 1.0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn ticks_terminate_for_huge_values() {
+        let lo = 1e17;
+        let ticks = ticks_for(lo, lo + 32.0, nice_step(32.0, 8.0));
+        assert!(!ticks.is_empty() && ticks.len() <= MAX_TICKS);
+        assert!(ticks_for(0.0, 1e300, 1e-300).len() <= MAX_TICKS);
+    }
+
+    #[test]
+    fn single_cell_crossing_gives_one_interpolated_segment() {
+        // Wiersz 0 = góra: lewa kolumna 0, prawa 2 – izolinia 0.5 przecina
+        // górną i dolną krawędź w 1/4 szerokości komórki.
+        let segments = contour_segments(&[0.0, 2.0, 0.0, 2.0], 2, 2, &[0.5]);
+        assert_eq!(segments, [[(0.25, 0.0), (0.25, 1.0)]]);
+        assert!(contour_segments(&[0.0, 2.0, 0.0, 2.0], 2, 2, &[3.0]).is_empty());
+    }
+
+    #[test]
+    fn saddle_pairing_follows_center_value() {
+        // a = c = 1 (lewy górny i prawy dolny), b = d = 0; średnia w środku 0.5.
+        let z = [1.0, 0.0, 0.0, 1.0];
+
+        // Środek ≥ poziomu: a i c połączone, odcięte narożniki b i d.
+        let high = contour_segments(&z, 2, 2, &[0.5]);
+        assert_eq!(high, [[(0.5, 0.0), (1.0, 0.5)], [(0.5, 1.0), (0.0, 0.5)]]);
+
+        // Środek < poziomu: odcięte narożniki a i c.
+        let low = contour_segments(&z, 2, 2, &[0.75]);
+        assert_eq!(low, [[(0.0, 0.25), (0.25, 0.0)], [(1.0, 0.75), (0.75, 1.0)]]);
+    }
+
+    #[test]
+    fn cells_with_non_finite_corner_are_skipped() {
+        // Dwie komórki obok siebie; lewa ma narożnik NaN, prawa jest poprawna.
+        let z = [f64::NAN, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let segments = contour_segments(&z, 3, 2, &[0.5]);
+        assert_eq!(segments, [[(1.5, 0.0), (1.5, 1.0)]]);
+        let z = [f64::INFINITY, 0.0, 0.0, 0.0];
+        assert!(contour_segments(&z, 2, 2, &[0.5]).is_empty());
+    }
+
+    #[test]
+    fn colormap_clamps_to_end_colors() {
+        let (first, last) = (Color32::from_rgb(68, 1, 84), Color32::from_rgb(253, 231, 37));
+        assert_eq!(colormap(0.0), first);
+        assert_eq!(colormap(1.0), last);
+        assert_eq!(colormap(-3.0), first);
+        assert_eq!(colormap(7.5), last);
+        assert_eq!(colormap(f64::NEG_INFINITY), first);
+        assert_eq!(colormap(f64::INFINITY), last);
+        assert_eq!(colormap(0.5), Color32::from_rgb(33, 145, 140));
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::expr::{Expr, ParseError};
+
+/// Domyślna funkcja celu – do maksymalizacji przez GA.
+pub const DEFAULT_SOURCE: &str = "(x + 5) * cos(2*x - 5) - 5";
+
+/// Domknięcie liczące wartość funkcji celu w punkcie (x1, …, xN).
+pub type ObjectiveFn = Arc<dyn Fn(&[f64]) -> f64 + Send + Sync>;
+
+/// Funkcja celu N zmiennych optymalizowana przez GA.
+///
+/// Może pochodzić z wyrażenia wpisanego przez użytkownika ([`Objective::from_expr`])
+/// albo z dowolnego domknięcia Rusta ([`Objective::new`]) – np. z testów
+/// lub własnych problemów wieloparametrowych.
+#[derive(Clone)]
+pub struct Objective {
+    label: String,
+    vars:  Vec<String>,
+    func:  ObjectiveFn,
+}
+
+impl Objective {
+    /// Tworzy funkcję celu z domknięcia; zmienne nazywane są x1..xN
+    /// (albo `x` dla funkcji jednej zmiennej).
+    pub fn new(
+        label: impl Into<String>,
+        dims: usize,
+        func: impl Fn(&[f64]) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        let vars = if dims == 1 {
+            vec!["x".to_string()]
+        } else {
+            (1..=dims).map(|n| format!("x{n}")).collect()
+        };
+        Self { label: label.into(), vars, func: Arc::new(func) }
+    }
+
+    /// Owija skompilowane wyrażenie; liczba zmiennych wynika z wyrażenia.
+    pub fn from_expr(expr: Expr) -> Self {
+        let label = expr.source().to_string();
+        let vars = expr.vars().to_vec();
+        Self { label, vars, func: Arc::new(move |xs: &[f64]| expr.eval_at(xs)) }
+    }
+
+    /// Parsuje wyrażenie (zmienne `x`, `y`, `z` albo `x1`..`xN`).
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        Expr::parse_auto(src).map(Self::from_expr)
+    }
+
+    /// Tekst wyrażenia albo nazwa funkcji wbudowanej.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Nazwy zmiennych w kolejności genomu.
+    pub fn vars(&self) -> &[String] {
+        &self.vars
+    }
+
+    /// Liczba zmiennych (wymiar przestrzeni przeszukiwania).
+    pub fn dims(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Wartość funkcji celu w punkcie `xs` (długość = [`Objective::dims`]).
+    pub fn eval(&self, xs: &[f64]) -> f64 {
+        (self.func)(xs)
+    }
+}
+
+impl fmt::Debug for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Objective")
+            .field("label", &self.label)
+            .field("vars", &self.vars)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Zwraca skompilowaną domyślną funkcję celu.
+pub fn default_objective() -> Objective {
+    Objective::parse(DEFAULT_SOURCE).expect("domyślna funkcja celu musi się parsować")
+}
+
+// ---------------------------------------------------------------------------
+// Klasyczne funkcje testowe N zmiennych (wszystkie do minimalizacji,
+// minimum globalne równe 0).
+// ---------------------------------------------------------------------------
+
+/// Funkcja sferyczna: Σ xᵢ², minimum w 0.
+pub fn sphere(dims: usize) -> Objective {
+    Objective::new(format!("sphere({dims})"), dims, |xs| xs.iter().map(|x| x * x).sum())
+}
+
+/// Funkcja Rastrigina: 10·N + Σ (xᵢ² − 10·cos(2π·xᵢ)), minimum w 0.
+pub fn rastrigin(dims: usize) -> Objective {
+    Objective::new(format!("rastrigin({dims})"), dims, |xs| {
+        let tau = std::f64::consts::TAU;
+        10.0 * xs.len() as f64 + xs.iter().map(|x| x * x - 10.0 * (tau * x).cos()).sum::<f64>()
+    })
+}
+
+/// Funkcja Rosenbrocka: Σ 100·(xᵢ₊₁ − xᵢ²)² + (1 − xᵢ)², minimum w (1, …, 1).
+pub fn rosenbrock(dims: usize) -> Objective {
+    Objective::new(format!("rosenbrock({dims})"), dims, |xs| {
+        xs.windows(2)
+            .map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2))
+            .sum()
+    })
+}
+
+/// Wbudowane funkcje testowe dostępne po nazwie (np. z linii poleceń).
+pub fn preset(name: &str, dims: usize) -> Option<Objective> {
+    match name {
+        "sphere"     => Some(sphere(dims)),
+        "rastrigin"  => Some(rastrigin(dims)),
+        "rosenbrock" => Some(rosenbrock(dims)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_names_variables_by_dimension() {
+        assert_eq!(Objective::new("f", 1, |xs| xs[0]).vars(), ["x"]);
+        let f = Objective::new("f", 3, |xs| xs.iter().sum());
+        assert_eq!(f.vars(), ["x1", "x2", "x3"]);
+        assert_eq!(f.dims(), 3);
+        assert_eq!(f.label(), "f");
+        assert_eq!(f.eval(&[1.0, 2.0, 3.0]), 6.0);
+    }
+
+    #[test]
+    fn parsed_expression_keeps_source_and_variables() {
+        let f = Objective::parse("x*y - z").unwrap();
+        assert_eq!(f.label(), "x*y - z");
+        assert_eq!(f.dims(), 3);
+        assert_eq!(f.eval(&[2.0, 3.0, 1.0]), 5.0);
+        assert!(Objective::parse("x +").is_err());
+        assert_eq!(default_objective().dims(), 1);
+    }
+
+    #[test]
+    fn presets_have_zero_global_minimum() {
+        for dims in [1, 2, 5] {
+            assert_eq!(sphere(dims).eval(&vec![0.0; dims]), 0.0);
+            assert!(rastrigin(dims).eval(&vec![0.0; dims]).abs() < 1e-12);
+            assert_eq!(rosenbrock(dims).eval(&vec![1.0; dims]), 0.0);
+        }
+        assert_eq!(sphere(2).eval(&[3.0, 4.0]), 25.0);
+        assert!((rastrigin(1).eval(&[0.5]) - 20.25).abs() < 1e-12);
+        assert_eq!(rosenbrock(2).eval(&[0.0, 1.0]), 101.0);
+    }
+
+    #[test]
+    fn preset_lookup_by_name() {
+        assert_eq!(preset("rastrigin", 4).unwrap().dims(), 4);
+        assert_eq!(preset("sphere", 2).unwrap().label(), "sphere(2)");
+        assert!(preset("ackley", 2).is_none());
+    }
+}
@@ -1,27 +1,7 @@
 use eframe::egui;
-
-// ---------------------------------------------------------------------------
-// Parametry GA przechowywane po zatwierdzeniu przez użytkownika
-// ---------------------------------------------------------------------------
-
-#[derive(Clone, Debug)]
-pub struct OptionsParams {
-    pub mutation_prob:  f64,
-    pub crossover_prob: f64,
-    pub tournament_k:   usize,
-    pub pop_size:       usize,
-}
-
-impl Default for OptionsParams {
-    fn default() -> Self {
-        Self {
-            mutation_prob:  0.05,
-            crossover_prob: 0.8,
-            tournament_k:   3,
-            pop_size:       20,
-        }
-    }
-}
+use genetictool2::{Direction, Encoding, FitnessTransform, OptionsParams, MAX_BITS, MIN_BITS};
+use genetictool2::{CrossoverMethod, RealCrossover, RealMutation, Replacement, Representation, SelectionMethod};
+use genetictool2::params::{random_seed, MAX_POP_SIZE, MAX_SEED};
 
 // ---------------------------------------------------------------------------
 // OptionsWindow – stan edytowalny, metoda show() rysuje okno
@@ -35,11 +15,12 @@ impl Default for OptionsParams {
 pub struct OptionsWindow {
     /// Czy okno jest aktualnie widoczne.
     pub open: bool,
-    // Wartości robocze (edytowane przez użytkownika, ale jeszcze niezatwierdzone).
-    mutation_prob:  f64,
-    crossover_prob: f64,
-    tournament_k:   usize,
-    pop_size:       usize,
+    /// Wartości robocze (edytowane przez użytkownika, ale jeszcze niezatwierdzone).
+    draft:          OptionsParams,
+    /// Nazwy zmiennych funkcji celu (etykiety wierszy dziedzin).
+    var_names:      Vec<String>,
+    /// Żądana liczba cyfr po przecinku dla przycisku "Dobierz bity".
+    precision:      u32,
     /// Zmierzona szerokość paska przycisków z poprzedniej klatki (do centrowania).
     btn_bar_width:  f32,
 }
@@ -49,20 +30,18 @@ impl OptionsWindow {
     pub fn new(params: &OptionsParams) -> Self {
         Self {
             open:           false,
-            mutation_prob:  params.mutation_prob,
-            crossover_prob: params.crossover_prob,
-            tournament_k:   params.tournament_k,
-            pop_size:       params.pop_size,
+            draft:          params.clone(),
+            var_names:      vec!["x".to_string()],
+            precision:      4,
             btn_bar_width:  0.0,
         }
     }
 
     /// Otwiera okno i kopiuje do niego aktualne parametry do edycji.
-    pub fn open_with(&mut self, params: &OptionsParams) {
-        self.mutation_prob  = params.mutation_prob;
-        self.crossover_prob = params.crossover_prob;
-        self.tournament_k   = params.tournament_k;
-        self.pop_size       = params.pop_size;
+    /// `var_names` to zmienne bieżącej funkcji celu (po jednej dziedzinie na zmienną).
+    pub fn open_with(&mut self, params: &OptionsParams, var_names: &[String]) {
+        self.draft          = params.clone();
+        self.var_names      = var_names.to_vec();
         self.btn_bar_width  = 0.0;
         self.open           = true;
     }
@@ -83,7 +62,7 @@ impl OptionsWindow {
         egui::Window::new("Opcje")
             .collapsible(false)
             .resizable(false)
-            .fixed_size([460.0, 170.0])
+            .min_width(460.0)
             .pivot(egui::Align2::CENTER_CENTER)
             .default_pos(center)
             .open(&mut self.open)
@@ -94,7 +73,7 @@ impl OptionsWindow {
                     .show(ui, |ui| {
                         ui.label("Prawdopodobieństwo mutacji (MUTATION_PROB):");
                         ui.add(
-                            egui::DragValue::new(&mut self.mutation_prob)
+                            egui::DragValue::new(&mut self.draft.mutation_prob)
                                 .speed(0.001)
                                 .range(0.0..=1.0),
                         );
@@ -102,15 +81,45 @@ impl OptionsWindow {
 
                         ui.label("Prawdopodobieństwo krzyżowania (CROSSOVER_PROB):");
                         ui.add(
-                            egui::DragValue::new(&mut self.crossover_prob)
+                            egui::DragValue::new(&mut self.draft.crossover_prob)
                                 .speed(0.001)
                                 .range(0.0..=1.0),
                         );
                         ui.end_row();
 
+                        let selection = &mut self.draft.selection;
+                        ui.label("Selekcja:");
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt("selection")
+                                .selected_text(selection.method.label())
+                                .show_ui(ui, |ui| {
+                                    for m in SelectionMethod::ALL {
+                                        ui.selectable_value(&mut selection.method, m, m.label());
+                                    }
+                                });
+                            // Parametr wybranej metody (rozmiar turnieju ma osobny wiersz).
+                            match selection.method {
+                                SelectionMethod::Rank => {
+                                    ui.label("s").on_hover_text("Nacisk selekcyjny: 1 = wszyscy równi, 2 = maksymalny");
+                                    ui.add(egui::DragValue::new(&mut selection.rank_pressure).speed(0.01).range(1.0..=2.0));
+                                }
+                                SelectionMethod::Truncation => {
+                                    ui.label("τ").on_hover_text("Ułamek najlepszych, spośród których losujemy");
+                                    ui.add(egui::DragValue::new(&mut selection.truncation).speed(0.01).range(0.01..=1.0));
+                                }
+                                SelectionMethod::Boltzmann => {
+                                    ui.label("T").on_hover_text("Temperatura: niższa = silniejsza presja");
+                                    ui.add(egui::DragValue::new(&mut selection.temperature).speed(0.01).range(0.001..=1000.0));
+                                }
+                                SelectionMethod::Tournament | SelectionMethod::Roulette | SelectionMethod::Sus => {}
+                            }
+                        });
+                        ui.end_row();
+
                         ui.label("Rozmiar turnieju (TOURNAMENT_K):");
-                        ui.add(
-                            egui::DragValue::new(&mut self.tournament_k)
+                        ui.add_enabled(
+                            selection.method == SelectionMethod::Tournament,
+                            egui::DragValue::new(&mut self.draft.tournament_k)
                                 .speed(0.1)
                                 .range(1..=20),
                         );
@@ -118,14 +127,300 @@ impl OptionsWindow {
 
                         ui.label("Rozmiar populacji (POP_SIZE):");
                         ui.add(
-                            egui::DragValue::new(&mut self.pop_size)
+                            egui::DragValue::new(&mut self.draft.pop_size)
                                 .speed(0.1)
-                                .range(2..=100),
+                                .range(2..=MAX_POP_SIZE),
+                        );
+                        ui.end_row();
+
+                        ui.label("Liczba elit (ELITISM):")
+                            .on_hover_text("Najlepsi przechodzą bez zmian do następnego pokolenia");
+                        ui.add(
+                            egui::DragValue::new(&mut self.draft.elitism)
+                                .speed(0.1)
+                                .range(0..=self.draft.pop_size - 1),
+                        );
+                        ui.end_row();
+
+                        let replacement = &mut self.draft.replacement;
+                        ui.label("Zastępowanie:");
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt("replacement")
+                                .selected_text(replacement.strategy.label())
+                                .show_ui(ui, |ui| {
+                                    for r in Replacement::ALL {
+                                        ui.selectable_value(&mut replacement.strategy, r, r.label());
+                                    }
+                                });
+                            match replacement.strategy {
+                                Replacement::MuPlusLambda | Replacement::MuCommaLambda => {
+                                    ui.label("λ").on_hover_text("Liczba dzieci na pokolenie");
+                                    ui.add(egui::DragValue::new(&mut replacement.lambda).speed(0.1).range(1..=1000));
+                                }
+                                Replacement::SteadyState => {
+                                    ui.label("n").on_hover_text("Liczba najgorszych zastępowanych w jednym kroku");
+                                    ui.add(egui::DragValue::new(&mut replacement.steady_count).speed(0.1).range(1..=100));
+                                }
+                                Replacement::Generational => {}
+                            }
+                        });
+                        ui.end_row();
+
+                        // Ziarno działa od następnego Resetu – ten sam seed
+                        // odtwarza cały przebieg bit w bit.
+                        ui.label("Ziarno generatora (SEED):")
+                            .on_hover_text("Obowiązuje od następnego Resetu");
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.draft.seed).speed(1.0).range(0..=MAX_SEED));
+                            if ui.button("🎲").on_hover_text("Nowe losowe ziarno").clicked() {
+                                self.draft.seed = random_seed();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Reprezentacja chromosomu:")
+                            .on_hover_text("Zmiana reprezentacji rozpoczyna nowy przebieg");
+                        ui.horizontal(|ui| {
+                            for r in Representation::ALL {
+                                ui.radio_value(&mut self.draft.representation, r, r.label());
+                            }
+                        });
+                        ui.end_row();
+
+                        // Kodowanie i liczba bitów dotyczą tylko reprezentacji binarnej,
+                        // operatory rzeczywiste – tylko rzeczywistej.
+                        let binary = self.draft.representation == Representation::Binary;
+
+                        ui.label("Kodowanie genomu:")
+                            .on_hover_text("Zmiana kodowania rozpoczyna nowy przebieg");
+                        ui.add_enabled_ui(binary, |ui| {
+                            egui::ComboBox::from_id_salt("encoding")
+                                .selected_text(self.draft.encoding.label())
+                                .show_ui(ui, |ui| {
+                                    for e in Encoding::ALL {
+                                        ui.selectable_value(&mut self.draft.encoding, e, e.label());
+                                    }
+                                });
+                        });
+                        ui.end_row();
+
+                        let crossover = &mut self.draft.crossover;
+                        ui.label("Krzyżowanie bitowe:");
+                        ui.add_enabled_ui(binary, |ui| {
+                            ui.horizontal(|ui| {
+                                egui::ComboBox::from_id_salt("crossover")
+                                    .selected_text(crossover.method.label())
+                                    .show_ui(ui, |ui| {
+                                        for c in CrossoverMethod::ALL {
+                                            ui.selectable_value(&mut crossover.method, c, c.label());
+                                        }
+                                    });
+                                match crossover.method {
+                                    CrossoverMethod::KPoint => {
+                                        ui.label("K").on_hover_text("Liczba punktów cięcia");
+                                        ui.add(egui::DragValue::new(&mut crossover.points).speed(0.1).range(1..=32));
+                                    }
+                                    CrossoverMethod::Uniform => {
+                                        ui.label("p").on_hover_text("Prawdopodobieństwo zamiany bitu");
+                                        ui.add(egui::DragValue::new(&mut crossover.swap_prob).speed(0.01).range(0.0..=1.0));
+                                    }
+                                    CrossoverMethod::OnePoint | CrossoverMethod::TwoPoint | CrossoverMethod::Shuffle => {}
+                                }
+                            });
+                        });
+                        ui.end_row();
+
+                        let real = &mut self.draft.real;
+                        ui.label("Krzyżowanie rzeczywiste:");
+                        ui.add_enabled_ui(!binary, |ui| {
+                            ui.horizontal(|ui| {
+                                egui::ComboBox::from_id_salt("real_crossover")
+                                    .selected_text(real.crossover.label())
+                                    .show_ui(ui, |ui| {
+                                        for c in RealCrossover::ALL {
+                                            ui.selectable_value(&mut real.crossover, c, c.label());
+                                        }
+                                    });
+                                match real.crossover {
+                                    RealCrossover::Arithmetic => {}
+                                    RealCrossover::Blx => {
+                                        ui.label("α");
+                                        ui.add(egui::DragValue::new(&mut real.blx_alpha).speed(0.01).range(0.0..=2.0));
+                                    }
+                                    RealCrossover::Sbx => {
+                                        ui.label("η");
+                                        ui.add(egui::DragValue::new(&mut real.sbx_eta).speed(0.1).range(0.0..=100.0));
+                                    }
+                                }
+                            });
+                        });
+                        ui.end_row();
+
+                        ui.label("Mutacja rzeczywista:");
+                        ui.add_enabled_ui(!binary, |ui| {
+                            ui.horizontal(|ui| {
+                                egui::ComboBox::from_id_salt("real_mutation")
+                                    .selected_text(real.mutation.label())
+                                    .show_ui(ui, |ui| {
+                                        for m in RealMutation::ALL {
+                                            ui.selectable_value(&mut real.mutation, m, m.label());
+                                        }
+                                    });
+                                match real.mutation {
+                                    RealMutation::Gaussian => {
+                                        ui.label("σ").on_hover_text("Ułamek szerokości dziedziny");
+                                        ui.add(egui::DragValue::new(&mut real.gauss_sigma).speed(0.001).range(0.001..=1.0));
+                                    }
+                                    RealMutation::Polynomial => {
+                                        ui.label("η");
+                                        ui.add(egui::DragValue::new(&mut real.poly_eta).speed(0.1).range(0.0..=100.0));
+                                    }
+                                }
+                            });
+                        });
+                        ui.end_row();
+
+                        ui.label("Kierunek optymalizacji:");
+                        ui.horizontal(|ui| {
+                            for d in Direction::ALL {
+                                ui.radio_value(&mut self.draft.fitness.direction, d, d.label());
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Przekształcenie fitness:");
+                        egui::ComboBox::from_id_salt("fitness_transform")
+                            .selected_text(self.draft.fitness.transform.label())
+                            .show_ui(ui, |ui| {
+                                for t in FitnessTransform::ALL {
+                                    ui.selectable_value(&mut self.draft.fitness.transform, t, t.label());
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Współczynnik skalowania (EXP_SCALE):");
+                        ui.add_enabled(
+                            self.draft.fitness.transform == FitnessTransform::Exponential,
+                            egui::DragValue::new(&mut self.draft.fitness.exp_scale)
+                                .speed(0.01)
+                                .range(0.001..=100.0),
                         );
                         ui.end_row();
                     });
 
-                ui.add_space(12.0);
+                // Dziedzina każdej zmiennej – przy wielu zmiennych lista się przewija.
+                ui.add_space(8.0);
+                ui.label("Dziedziny zmiennych:")
+                    .on_hover_text("Zmiana dziedziny lub liczby bitów rozpoczyna nowy przebieg");
+                let binary = self.draft.representation == Representation::Binary;
+                egui::ScrollArea::vertical()
+                    .id_salt("options_vars")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("options_vars_grid")
+                            .num_columns(4)
+                            .spacing([12.0, 6.0])
+                            .show(ui, |ui| {
+                                for (name, v) in self.var_names.iter().zip(self.draft.vars.iter_mut()) {
+                                    ui.label(egui::RichText::new(name).monospace());
+                                    ui.horizontal(|ui| {
+                                        ui.add(egui::DragValue::new(&mut v.min).speed(0.1));
+                                        ui.label("…");
+                                        ui.add(egui::DragValue::new(&mut v.max).speed(0.1));
+                                    });
+                                    ui.add_enabled(
+                                        binary,
+                                        egui::DragValue::new(&mut v.bits)
+                                            .range(MIN_BITS..=MAX_BITS)
+                                            .suffix(" bit"),
+                                    );
+                                    // Rozdzielczość ma sens tylko dla poprawnej dziedziny
+                                    // i reprezentacji binarnej.
+                                    let resolution = if binary && v.min < v.max {
+                                        format!("Δ = {:.3e}", v.resolution())
+                                    } else {
+                                        "Δ = —".to_string()
+                                    };
+                                    ui.label(egui::RichText::new(resolution).monospace())
+                                        .on_hover_text("Odległość między sąsiednimi wartościami, które da się zakodować");
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                // Liczba bitów wyliczana z żądanej precyzji, osobno dla każdej dziedziny.
+                ui.horizontal(|ui| {
+                    ui.label("Precyzja:");
+                    ui.add(egui::DragValue::new(&mut self.precision).range(0..=15).suffix(" cyfr po przecinku"));
+                    if ui.add_enabled(binary, egui::Button::new("Dobierz bity")).clicked() {
+                        for v in self.draft.vars.iter_mut().filter(|v| v.min < v.max) {
+                            v.bits = v.bits_for_precision(self.precision);
+                        }
+                    }
+                });
+
+                // Warunki zatrzymania – każdy z osobnym włącznikiem, wartość
+                // pozostaje zapamiętana po wyłączeniu.
+                ui.add_space(8.0);
+                ui.label("Warunki zatrzymania trybu Auto:")
+                    .on_hover_text("Auto zatrzymuje się po spełnieniu dowolnego włączonego warunku");
+                let stop = &mut self.draft.stop;
+                egui::Grid::new("options_stop_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.checkbox(&mut stop.max_generations.enabled, "Pokolenie:");
+                        ui.add_enabled(
+                            stop.max_generations.enabled,
+                            egui::DragValue::new(&mut stop.max_generations.value).speed(1.0).range(1..=1_000_000),
+                        );
+                        ui.end_row();
+
+                        ui.checkbox(&mut stop.target_value.enabled, "Wartość docelowa f:")
+                            .on_hover_text("Przy minimalizacji: najlepsze f spada do tej wartości");
+                        ui.add_enabled(
+                            stop.target_value.enabled,
+                            egui::DragValue::new(&mut stop.target_value.value).speed(0.01),
+                        );
+                        ui.end_row();
+
+                        ui.checkbox(&mut stop.stagnation.enabled, "Brak poprawy przez:");
+                        ui.add_enabled(
+                            stop.stagnation.enabled,
+                            egui::DragValue::new(&mut stop.stagnation.value)
+                                .speed(1.0)
+                                .range(1..=100_000)
+                                .suffix(" pokoleń"),
+                        );
+                        ui.end_row();
+
+                        ui.checkbox(&mut stop.min_diversity.enabled, "Różnorodność poniżej:")
+                            .on_hover_text("Średnie odchylenie standardowe zmiennych jako ułamek szerokości dziedziny");
+                        ui.add_enabled(
+                            stop.min_diversity.enabled,
+                            egui::DragValue::new(&mut stop.min_diversity.value).speed(0.0001).range(0.0..=1.0),
+                        );
+                        ui.end_row();
+
+                        ui.checkbox(&mut stop.time_limit.enabled, "Limit czasu:");
+                        ui.add_enabled(
+                            stop.time_limit.enabled,
+                            egui::DragValue::new(&mut stop.time_limit.value)
+                                .speed(1.0)
+                                .range(0.1..=86_400.0)
+                                .suffix(" s"),
+                        );
+                        ui.end_row();
+                    });
+
+                // Błędne parametry blokują OK (i Enter) – komunikat mówi dlaczego.
+                let validation = self.draft.validate();
+                ui.add_space(6.0);
+                match &validation {
+                    Err(msg) => ui.colored_label(egui::Color32::from_rgb(220, 60, 60), msg),
+                    Ok(())   => ui.label(""),
+                };
+                ui.add_space(6.0);
                 let available_width = ui.available_width();
 
                 // Klatka 0: btn_bar_width==0, lewy margines=0, wszystkie przyciski
@@ -138,7 +433,7 @@ impl OptionsWindow {
 
                 let btn_row = ui.horizontal(|ui| {
                     ui.add_space(left_margin);
-                    if ui.add(egui::Button::new("OK").shortcut_text("Enter")).clicked() {
+                    if ui.add_enabled(validation.is_ok(), egui::Button::new("OK").shortcut_text("Enter")).clicked() {
                         confirmed = true;
                     }
                     if ui.add(egui::Button::new("Anuluj").shortcut_text("Esc")).clicked() {
@@ -147,11 +442,8 @@ impl OptionsWindow {
                     if ui.add(egui::Button::new("Reset").shortcut_text("Alt+R")).clicked()
                         || ctx.input_mut(|i| i.consume_key(egui::Modifiers::ALT, egui::Key::R))
                     {
-                        let d = OptionsParams::default();
-                        self.mutation_prob  = d.mutation_prob;
-                        self.crossover_prob = d.crossover_prob;
-                        self.tournament_k   = d.tournament_k;
-                        self.pop_size       = d.pop_size;
+                        self.draft = OptionsParams::default();
+                        self.draft.resize_vars(self.var_names.len());
                         ctx.request_repaint();
                     }
                 });
@@ -162,7 +454,9 @@ impl OptionsWindow {
                 }
 
                 // Enter = OK, Escape = Anuluj
-                if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)) {
+                if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter))
+                    && validation.is_ok()
+                {
                     confirmed = true;
                 }
                 if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
@@ -172,12 +466,7 @@ impl OptionsWindow {
 
         if confirmed {
             self.open = false;
-            return Some(OptionsParams {
-                mutation_prob:  self.mutation_prob,
-                crossover_prob: self.crossover_prob,
-                tournament_k:   self.tournament_k,
-                pop_size:       self.pop_size,
-            });
+            return Some(self.draft.clone());
         }
 
         if cancelled {
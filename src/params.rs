@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::{Representation, VarSpec, MAX_BITS, MIN_BITS};
+use crate::crossover::CrossoverParams;
+use crate::encoding::Encoding;
+use crate::fitness::FitnessMode;
+use crate::real::RealOperators;
+use crate::replacement::ReplacementParams;
+use crate::selection::SelectionParams;
+use crate::stopping::StopCriteria;
+
+// ---------------------------------------------------------------------------
+// Parametry GA przechowywane po zatwierdzeniu przez użytkownika
+// ---------------------------------------------------------------------------
+
+/// Największy dopuszczalny rozmiar populacji.
+pub const MAX_POP_SIZE: usize = 10_000;
+
+/// Największe dopuszczalne ziarno. Zakres u32 wystarcza do odtwarzania
+/// przebiegów, a pliki TOML (przebiegi, ustawienia) nie zapisują liczb
+/// całkowitych większych niż i64::MAX.
+pub const MAX_SEED: u64 = u32::MAX as u64;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OptionsParams {
+    pub mutation_prob:  f64,
+    pub crossover_prob: f64,
+    /// Operator krzyżowania genomu bitowego.
+    pub crossover:      CrossoverParams,
+    pub tournament_k:   usize,
+    /// Metoda selekcji rodziców i jej parametry.
+    pub selection:      SelectionParams,
+    pub pop_size:       usize,
+    /// Liczba najlepszych osobników przechodzących bez zmian do następnego
+    /// pokolenia (0..pop_size).
+    pub elitism:        usize,
+    /// Strategia zastępowania populacji (pokoleniowe, (μ+λ), (μ,λ), stacjonarne).
+    pub replacement:    ReplacementParams,
+    /// Ziarno generatora liczb losowych przebiegu.
+    pub seed:           u64,
+    /// Dziedzina i precyzja każdej zmiennej funkcji celu – wspólne dla
+    /// dekodowania chromosomów, populacji startowej i osi wykresu.
+    pub vars:           Vec<VarSpec>,
+    /// Reprezentacja chromosomu: ciąg bitów albo wektor liczb rzeczywistych.
+    pub representation: Representation,
+    /// Kodowanie odcinków genomu (dwójkowe albo kod Graya) – tylko dla
+    /// reprezentacji binarnej.
+    pub encoding:       Encoding,
+    /// Krzyżowanie i mutacja reprezentacji rzeczywistej.
+    pub real:           RealOperators,
+    /// Kierunek optymalizacji i przekształcenie fitness.
+    pub fitness:        FitnessMode,
+    /// Warunki zatrzymania trybu Auto.
+    pub stop:           StopCriteria,
+}
+
+impl Default for OptionsParams {
+    fn default() -> Self {
+        Self {
+            mutation_prob:  0.05,
+            crossover_prob: 0.8,
+            crossover:      CrossoverParams::default(),
+            tournament_k:   3,
+            selection:      SelectionParams::default(),
+            pop_size:       20,
+            elitism:        1,
+            replacement:    ReplacementParams::default(),
+            seed:           12345,
+            vars:           vec![VarSpec::default()],
+            representation: Representation::Binary,
+            encoding:       Encoding::Binary,
+            real:           RealOperators::default(),
+            fitness:        FitnessMode::default(),
+            stop:           StopCriteria::default(),
+        }
+    }
+}
+
+impl OptionsParams {
+    /// Sprawdza spójność parametrów; zwraca opis pierwszego napotkanego błędu.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(2..=MAX_POP_SIZE).contains(&self.pop_size) {
+            return Err(format!("rozmiar populacji musi należeć do [2, {MAX_POP_SIZE}]"));
+        }
+        if self.seed > MAX_SEED {
+            return Err(format!("ziarno musi należeć do [0, {MAX_SEED}]"));
+        }
+        if !(0.0..=1.0).contains(&self.mutation_prob) || !(0.0..=1.0).contains(&self.crossover_prob) {
+            return Err("prawdopodobieństwa muszą należeć do [0, 1]".into());
+        }
+        if self.tournament_k < 1 {
+            return Err("rozmiar turnieju musi wynosić co najmniej 1".into());
+        }
+        if self.vars.is_empty() {
+            return Err("funkcja celu musi mieć co najmniej jedną zmienną".into());
+        }
+        for (i, v) in self.vars.iter().enumerate() {
+            let n = i + 1;
+            if !v.min.is_finite() || !v.max.is_finite() {
+                return Err(format!("zmienna {n}: granice dziedziny muszą być skończone"));
+            }
+            if v.min >= v.max {
+                return Err(format!("zmienna {n}: dolna granica musi być mniejsza od górnej"));
+            }
+            if !(MIN_BITS..=MAX_BITS).contains(&v.bits) {
+                return Err(format!("zmienna {n}: liczba bitów musi należeć do [{MIN_BITS}, {MAX_BITS}]"));
+            }
+        }
+        if self.elitism >= self.pop_size {
+            return Err("liczba elit musi być mniejsza od rozmiaru populacji".into());
+        }
+        self.replacement.validate(self.pop_size, self.elitism)?;
+        self.selection.validate()?;
+        self.crossover.validate()?;
+        self.real.validate()?;
+        self.stop.validate()?;
+        if !(self.fitness.exp_scale > 0.0 && self.fitness.exp_scale.is_finite()) {
+            return Err("współczynnik skalowania wykładniczego musi być dodatni".into());
+        }
+        Ok(())
+    }
+
+    /// Czy zmiana z `self` na `other` wymaga nowego przebiegu (inaczej
+    /// populacja byłaby zdekodowana w innej dziedzinie, precyzji,
+    /// kodowaniu lub reprezentacji niż wykres i nowe chromosomy).
+    pub fn domain_changed(&self, other: &OptionsParams) -> bool {
+        self.vars != other.vars
+            || self.encoding != other.encoding
+            || self.representation != other.representation
+    }
+
+    /// Krótki opis operatorów GA do podsumowania pokolenia, np.
+    /// "selekcja: Turniejowa, krzyżowanie: Dwupunktowe, mutacja: Bitowa, …".
+    pub fn operators_summary(&self) -> String {
+        let (crossover, mutation) = match self.representation {
+            Representation::Binary => (self.crossover.method.label(), "Bitowa"),
+            Representation::Real   => (self.real.crossover.label(), self.real.mutation.label()),
+        };
+        format!(
+            "selekcja: {}, krzyżowanie: {}, mutacja: {}, zastępowanie: {}, elity: {}",
+            self.selection.method.label(),
+            crossover,
+            mutation,
+            self.replacement.strategy.label(),
+            self.elitism
+        )
+    }
+
+    /// Dopasowuje liczbę zmiennych do wymiaru funkcji celu: istniejące
+    /// zostają, nowe dostają dziedzinę ostatniej zmiennej.
+    pub fn resize_vars(&mut self, dims: usize) {
+        let last = self.vars.last().cloned().unwrap_or_default();
+        self.vars.resize(dims.max(1), last);
+    }
+}
+
+/// Losuje nowe ziarno z zakresu [0, [`MAX_SEED`]] – krótką liczbę łatwo
+/// przepisać z ekranu, żeby odtworzyć przebieg.
+pub fn random_seed() -> u64 {
+    rand::random::<u32>() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(OptionsParams::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn seed_is_limited_to_u32_range() {
+        let mut params = OptionsParams { seed: MAX_SEED, ..OptionsParams::default() };
+        assert_eq!(params.validate(), Ok(()));
+        assert!(toml::to_string(&params).is_ok());
+        params.seed = MAX_SEED + 1;
+        assert!(params.validate().is_err());
+        assert!((0..100).all(|_| random_seed() <= MAX_SEED));
+    }
+
+    #[test]
+    fn population_size_bounds() {
+        for (size, ok) in [(1, false), (2, true), (MAX_POP_SIZE, true), (MAX_POP_SIZE + 1, false)] {
+            let params = OptionsParams { pop_size: size, elitism: 0, ..OptionsParams::default() };
+            assert_eq!(params.validate().is_ok(), ok, "pop_size = {size}");
+        }
+    }
+
+    #[test]
+    fn domain_is_validated() {
+        let with_var = |min, max, bits| {
+            let mut params = OptionsParams::default();
+            params.vars[0] = VarSpec { min, max, bits };
+            params.validate()
+        };
+        assert_eq!(with_var(-1.0, 1.0, MIN_BITS), Ok(()));
+        assert_eq!(with_var(-1.0, 1.0, MAX_BITS), Ok(()));
+        assert!(with_var(1.0, 1.0, 16).unwrap_err().contains("zmienna 1"));
+        assert!(with_var(2.0, 1.0, 16).is_err());
+        assert!(with_var(f64::NEG_INFINITY, 1.0, 16).is_err());
+        assert!(with_var(0.0, f64::NAN, 16).is_err());
+        assert!(with_var(0.0, 1.0, MIN_BITS - 1).is_err());
+        assert!(with_var(0.0, 1.0, MAX_BITS + 1).is_err());
+
+        let params = OptionsParams { vars: Vec::new(), ..OptionsParams::default() };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn domain_change_requires_new_run() {
+        let params = OptionsParams::default();
+        let mut other = params.clone();
+        other.mutation_prob = 0.5;
+        assert!(!params.domain_changed(&other));
+        other.vars[0].max += 1.0;
+        assert!(params.domain_changed(&other));
+    }
+}
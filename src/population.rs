@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::{Chromosome, Representation, VarSpec};
+use crate::fitness::FitnessMode;
+use crate::objective::Objective;
+use crate::params::OptionsParams;
+
+/// Cała populacja: zbiór chromosomów + metadane bieżącego pokolenia.
+///
+/// Niezmiennik: `chromosomes` są posortowane malejąco po fitness,
+/// więc najlepszy osobnik jest zawsze pierwszy.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Population {
+    pub chromosomes: Vec<Chromosome>,
+    /// Numer aktualnego pokolenia (0 = populacja startowa).
+    pub generation: usize,
+    /// Numer, który dostanie następny nowo utworzony osobnik
+    /// (patrz [`crate::genealogy::Origin`]).
+    pub next_id: u64,
+}
+
+impl Population {
+    /// Tworzy losową populację startową (rozmiar, dziedzina i reprezentacja
+    /// z `params`), losując geny z podanego generatora. Osobniki dostają
+    /// kolejne numery od 0.
+    pub fn random<R: Rng>(params: &OptionsParams, func: &Objective, rng: &mut R) -> Self {
+        let chromosomes: Vec<Chromosome> = (0..params.pop_size)
+            .map(|i| {
+                let mut c = match params.representation {
+                    Representation::Binary => Chromosome::random(&params.vars, params.encoding, rng),
+                    Representation::Real   => Chromosome::random_real(&params.vars, rng),
+                };
+                c.evaluate(func);
+                c.origin.id = i as u64;
+                c
+            })
+            .collect();
+
+        Self::from_chromosomes(chromosomes, 0, &params.fitness)
+    }
+
+    /// Buduje populację z chromosomów o policzonej wartości funkcji celu:
+    /// przypisuje fitness i przywraca niezmiennik sortowania. Numeracja
+    /// nowych osobników zaczyna się za największym istniejącym numerem.
+    pub fn from_chromosomes(chromosomes: Vec<Chromosome>, generation: usize, mode: &FitnessMode) -> Self {
+        let next_id = chromosomes.iter().map(|c| c.origin.id + 1).max().unwrap_or(0);
+        let mut pop = Self { chromosomes, generation, next_id };
+        pop.rescore(mode);
+        pop
+    }
+
+    /// Przelicza fitness całej populacji (np. po zmianie kierunku optymalizacji)
+    /// i sortuje ją ponownie.
+    pub fn rescore(&mut self, mode: &FitnessMode) {
+        mode.assign(&mut self.chromosomes);
+        // Sortujemy malejąco po fitness – najlepszy na górze listy.
+        self.chromosomes.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
+    }
+
+    /// Zwraca najlepszy chromosom (po sortowaniu zawsze pierwszy).
+    pub fn best(&self) -> Option<&Chromosome> {
+        self.chromosomes.first()
+    }
+
+    /// Zwraca najgorszy chromosom (po sortowaniu zawsze ostatni).
+    pub fn worst(&self) -> Option<&Chromosome> {
+        self.chromosomes.last()
+    }
+
+    /// Średnia wartość funkcji celu w populacji (NaN dla pustej populacji).
+    pub fn mean_value(&self) -> f64 {
+        let sum: f64 = self.chromosomes.iter().map(|c| c.value).sum();
+        sum / self.chromosomes.len() as f64
+    }
+
+    /// Rozrzut populacji w przestrzeni zmiennych: odchylenie standardowe
+    /// każdej zmiennej podzielone przez szerokość jej dziedziny, uśrednione
+    /// po zmiennych. 0 = wszyscy w jednym punkcie.
+    pub fn spread(&self, vars: &[VarSpec]) -> f64 {
+        let n = self.chromosomes.len() as f64;
+        if vars.is_empty() || n == 0.0 {
+            return 0.0;
+        }
+        let total: f64 = vars
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let mean = self.chromosomes.iter().map(|c| c.xs[i]).sum::<f64>() / n;
+                let var = self.chromosomes.iter().map(|c| (c.xs[i] - mean).powi(2)).sum::<f64>() / n;
+                var.sqrt() / (v.max - v.min)
+            })
+            .sum();
+        total / vars.len() as f64
+    }
+
+    /// Częstość jedynek na każdej pozycji genomu bitowego (ułamek populacji).
+    /// Pozycja z częstością 0 albo 1 jest "utrwalona" – krzyżowanie już jej
+    /// nie zmieni. Pusty wektor dla reprezentacji rzeczywistej.
+    pub fn allele_frequencies(&self) -> Vec<f64> {
+        let n = self.chromosomes.len() as f64;
+        self.ones_per_bit().into_iter().map(|k| k as f64 / n).collect()
+    }
+
+    /// Średnia odległość Hamminga między parami osobników jako ułamek
+    /// długości genomu (0 = wszyscy identyczni). NaN dla reprezentacji
+    /// rzeczywistej.
+    pub fn mean_hamming(&self) -> f64 {
+        let ones = self.ones_per_bit();
+        let n = self.chromosomes.len();
+        if ones.is_empty() {
+            return f64::NAN;
+        }
+        if n < 2 {
+            return 0.0;
+        }
+        // Zamiast porównywać wszystkie pary: na pozycji z k jedynkami
+        // różni się dokładnie k·(n − k) par.
+        let differing: usize = ones.iter().map(|&k| k * (n - k)).sum();
+        let pairs = n * (n - 1) / 2;
+        differing as f64 / pairs as f64 / ones.len() as f64
+    }
+
+    /// Liczba różnych genotypów w populacji (ciągów bitów albo, w reprezentacji
+    /// rzeczywistej, wektorów x).
+    pub fn unique_genotypes(&self) -> usize {
+        if self.chromosomes.iter().all(|c| c.genes.is_empty()) {
+            let distinct: HashSet<Vec<u64>> = self.chromosomes
+                .iter()
+                .map(|c| c.xs.iter().map(|x| x.to_bits()).collect())
+                .collect();
+            return distinct.len();
+        }
+        let distinct: HashSet<&[bool]> = self.chromosomes.iter().map(|c| c.genes.as_slice()).collect();
+        distinct.len()
+    }
+
+    // Liczba jedynek na każdej pozycji genomu.
+    fn ones_per_bit(&self) -> Vec<usize> {
+        let len = self.chromosomes.first().map_or(0, |c| c.genes.len());
+        let mut ones = vec![0; len];
+        for c in &self.chromosomes {
+            for (k, _) in c.genes.iter().enumerate().filter(|&(_, &g)| g) {
+                ones[k] += 1;
+            }
+        }
+        ones
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::Encoding;
+    use crate::objective;
+
+    #[test]
+    fn random_population_is_sorted_and_numbered() {
+        let params = OptionsParams::default();
+        let pop = Population::random(&params, &objective::default_objective(), &mut crate::rng_from_seed(7));
+        assert_eq!(pop.chromosomes.len(), params.pop_size);
+        assert_eq!(pop.generation, 0);
+        assert_eq!(pop.next_id, params.pop_size as u64);
+        assert!(pop.chromosomes.windows(2).all(|w| w[0].fitness >= w[1].fitness));
+        assert_eq!(pop.best().unwrap().fitness, pop.chromosomes[0].fitness);
+    }
+
+    #[test]
+    fn from_chromosomes_continues_numbering_after_largest_id() {
+        let vars = [VarSpec::default()];
+        let chromosomes: Vec<Chromosome> = [(3.0, 4), (1.0, 9)]
+            .into_iter()
+            .map(|(x, id)| {
+                let mut c = Chromosome::from_xs(vec![x], &vars);
+                c.value = x;
+                c.origin.id = id;
+                c
+            })
+            .collect();
+        let pop = Population::from_chromosomes(chromosomes, 5, &FitnessMode::default());
+        assert_eq!(pop.next_id, 10);
+        assert_eq!(pop.best().unwrap().value, 3.0);
+        assert_eq!(pop.mean_value(), 2.0);
+    }
+
+    fn binary(genomes: &[&str]) -> Population {
+        let vars = [VarSpec { min: 0.0, max: 15.0, bits: 4 }];
+        let chromosomes = genomes
+            .iter()
+            .map(|g| Chromosome::from_genes(g.chars().map(|b| b == '1').collect(), &vars, Encoding::Binary))
+            .collect();
+        Population::from_chromosomes(chromosomes, 0, &FitnessMode::default())
+    }
+
+    #[test]
+    fn hamming_and_allele_frequencies_of_bit_genomes() {
+        let pop = binary(&["0000", "1111", "0011"]);
+        assert_eq!(pop.allele_frequencies(), [1.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0]);
+        // Pary: 4, 2 i 2 różne bity z 4.
+        assert!((pop.mean_hamming() - 8.0 / 3.0 / 4.0).abs() < 1e-12);
+        assert_eq!(pop.unique_genotypes(), 3);
+
+        let same = binary(&["0101", "0101"]);
+        assert_eq!(same.mean_hamming(), 0.0);
+        assert_eq!(same.unique_genotypes(), 1);
+        assert_eq!(binary(&["0101"]).mean_hamming(), 0.0);
+    }
+
+    #[test]
+    fn spread_is_relative_to_domain_width() {
+        let vars = [VarSpec { min: 0.0, max: 15.0, bits: 4 }];
+        let pop = binary(&["0000", "1111"]);
+        assert_eq!(pop.spread(&vars), 0.5);
+        assert_eq!(binary(&["0110", "0110"]).spread(&vars), 0.0);
+    }
+
+    #[test]
+    fn real_genomes_have_no_bit_metrics() {
+        let vars = [VarSpec::default(), VarSpec::default()];
+        let chromosomes = [[1.0, 2.0], [1.0, 2.0], [1.0, -2.0]]
+            .iter()
+            .map(|xs| Chromosome::from_xs(xs.to_vec(), &vars))
+            .collect();
+        let pop = Population::from_chromosomes(chromosomes, 0, &FitnessMode::default());
+        assert!(pop.mean_hamming().is_nan());
+        assert!(pop.allele_frequencies().is_empty());
+        assert_eq!(pop.unique_genotypes(), 2);
+        // Druga zmienna: odchylenie √(32/9) na szerokości 20, pierwsza: 0.
+        assert!((pop.spread(&vars) - (32.0_f64 / 9.0).sqrt() / 20.0 / 2.0).abs() < 1e-12);
+    }
+}
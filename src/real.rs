@@ -0,0 +1,300 @@
+// ---------------------------------------------------------------------------
+// Operatory reprezentacji rzeczywistej
+//
+// W reprezentacji rzeczywistej genami są bezpośrednio wartości zmiennych
+// x1..xN (`Chromosome::xs`) – bez kodowania bitowego. Krzyżowanie miesza
+// liczby rodziców, a mutacja dodaje do nich niewielkie zaburzenie.
+// Wynik zawsze przycinany jest do dziedziny (patrz `Chromosome::from_xs`).
+// ---------------------------------------------------------------------------
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::VarSpec;
+
+/// Krzyżowanie dwóch wektorów liczb rzeczywistych.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RealCrossover {
+    /// Średnia ważona rodziców z losową wagą λ ∈ [0, 1].
+    Arithmetic,
+    /// BLX-α: każdy gen losowany z przedziału rodziców poszerzonego o α·d.
+    Blx,
+    /// SBX: symulowane krzyżowanie binarne z indeksem rozkładu η.
+    Sbx,
+}
+
+impl RealCrossover {
+    pub const ALL: [RealCrossover; 3] = [RealCrossover::Arithmetic, RealCrossover::Blx, RealCrossover::Sbx];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RealCrossover::Arithmetic => "Arytmetyczne",
+            RealCrossover::Blx        => "BLX-α",
+            RealCrossover::Sbx        => "SBX",
+        }
+    }
+}
+
+/// Mutacja wektora liczb rzeczywistych.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RealMutation {
+    /// Dodaje szum normalny N(0, σ·(max − min)).
+    Gaussian,
+    /// Ograniczona mutacja wielomianowa (Deb) z indeksem rozkładu η;
+    /// wynik nie wychodzi poza dziedzinę.
+    Polynomial,
+}
+
+impl RealMutation {
+    pub const ALL: [RealMutation; 2] = [RealMutation::Gaussian, RealMutation::Polynomial];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RealMutation::Gaussian   => "Gaussowska",
+            RealMutation::Polynomial => "Wielomianowa",
+        }
+    }
+}
+
+/// Operatory reprezentacji rzeczywistej wraz z ich parametrami.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RealOperators {
+    pub crossover:   RealCrossover,
+    pub mutation:    RealMutation,
+    /// α krzyżowania BLX-α (typowo 0.5).
+    pub blx_alpha:   f64,
+    /// η krzyżowania SBX – im większe, tym dzieci bliżej rodziców.
+    pub sbx_eta:     f64,
+    /// σ mutacji gaussowskiej jako ułamek szerokości dziedziny.
+    pub gauss_sigma: f64,
+    /// η mutacji wielomianowej – im większe, tym mniejsze zaburzenia.
+    pub poly_eta:    f64,
+}
+
+impl Default for RealOperators {
+    fn default() -> Self {
+        Self {
+            crossover:   RealCrossover::Blx,
+            mutation:    RealMutation::Gaussian,
+            blx_alpha:   0.5,
+            sbx_eta:     15.0,
+            gauss_sigma: 0.1,
+            poly_eta:    20.0,
+        }
+    }
+}
+
+impl RealOperators {
+    /// Sprawdza parametry operatorów; zwraca opis pierwszego błędu.
+    pub fn validate(&self) -> Result<(), String> {
+        let non_negative = |v: f64| v >= 0.0 && v.is_finite();
+        if !non_negative(self.blx_alpha) {
+            return Err("α krzyżowania BLX nie może być ujemne".into());
+        }
+        if !non_negative(self.sbx_eta) || !non_negative(self.poly_eta) {
+            return Err("indeksy rozkładu η nie mogą być ujemne".into());
+        }
+        if !(self.gauss_sigma > 0.0 && self.gauss_sigma.is_finite()) {
+            return Err("σ mutacji gaussowskiej musi być dodatnie".into());
+        }
+        Ok(())
+    }
+
+    /// Tworzy dwoje dzieci z genów rodziców `a` i `b`.
+    pub fn crossover<R: Rng>(&self, a: &[f64], b: &[f64], rng: &mut R) -> (Vec<f64>, Vec<f64>) {
+        let (mut child_a, mut child_b) = (Vec::with_capacity(a.len()), Vec::with_capacity(a.len()));
+        match self.crossover {
+            // -- Arytmetyczne: dzieci leżą na odcinku między rodzicami,
+            //    symetrycznie (wagi λ i 1 − λ) ------------------------------
+            RealCrossover::Arithmetic => {
+                let lambda = rng.gen_range(0.0..=1.0);
+                for (x, y) in a.iter().zip(b) {
+                    child_a.push(lambda * x + (1.0 - lambda) * y);
+                    child_b.push((1.0 - lambda) * x + lambda * y);
+                }
+            }
+            // -- BLX-α: przedział [min, max] rodziców poszerzony z obu stron
+            //    o α·d, gdzie d to odległość między nimi. Dzięki poszerzeniu
+            //    dzieci mogą wyjść poza rodziców i populacja się nie "zwija".
+            //    Każde dziecko losowane jest niezależnie.
+            RealCrossover::Blx => {
+                for (&x, &y) in a.iter().zip(b) {
+                    let (lo, hi) = (x.min(y), x.max(y));
+                    let d = self.blx_alpha * (hi - lo);
+                    if d > 0.0 {
+                        child_a.push(rng.gen_range(lo - d..=hi + d));
+                        child_b.push(rng.gen_range(lo - d..=hi + d));
+                    } else {
+                        child_a.push(x);
+                        child_b.push(y);
+                    }
+                }
+            }
+            // -- SBX: naśladuje krzyżowanie jednopunktowe na bitach – dzieci
+            //    z dużym prawdopodobieństwem leżą blisko rodziców, symetrycznie
+            //    względem ich średniej, a rozrzut zależy od odległości między nimi.
+            RealCrossover::Sbx => {
+                let exp = 1.0 / (self.sbx_eta + 1.0);
+                for (&x, &y) in a.iter().zip(b) {
+                    let u: f64 = rng.gen_range(0.0..1.0);
+                    let beta = if u <= 0.5 {
+                        (2.0 * u).powf(exp)
+                    } else {
+                        (1.0 / (2.0 * (1.0 - u))).powf(exp)
+                    };
+                    child_a.push(0.5 * ((1.0 + beta) * x + (1.0 - beta) * y));
+                    child_b.push(0.5 * ((1.0 - beta) * x + (1.0 + beta) * y));
+                }
+            }
+        }
+        (child_a, child_b)
+    }
+
+    /// Mutuje każdy gen z prawdopodobieństwem `prob`. Zaburzenie skalowane
+    /// jest szerokością dziedziny danej zmiennej. Zwraca indeksy zmutowanych genów.
+    pub fn mutate<R: Rng>(&self, xs: &mut [f64], vars: &[VarSpec], prob: f64, rng: &mut R) -> Vec<usize> {
+        let mut mutated = Vec::new();
+        for (i, (x, v)) in xs.iter_mut().zip(vars).enumerate() {
+            if !rng.gen_bool(prob) {
+                continue;
+            }
+            mutated.push(i);
+            let width = v.max - v.min;
+            *x = match self.mutation {
+                RealMutation::Gaussian   => *x + standard_normal(rng) * self.gauss_sigma * width,
+                RealMutation::Polynomial => polynomial_mutation(*x, v, self.poly_eta, rng),
+            };
+        }
+        mutated
+    }
+}
+
+// Ograniczona mutacja wielomianowa Deba: rozkład zaburzenia zależy od
+// odległości genu do granic dziedziny (δ₁, δ₂), więc wynik zawsze leży
+// w [min, max] – przy granicy kroki w jej stronę są odpowiednio krótsze,
+// zamiast lądować poza dziedziną i być przycinane do niej.
+fn polynomial_mutation<R: Rng>(x: f64, v: &VarSpec, eta: f64, rng: &mut R) -> f64 {
+    let width = v.max - v.min;
+    let x = x.clamp(v.min, v.max);
+    let (delta1, delta2) = ((x - v.min) / width, (v.max - x) / width);
+    let exp = 1.0 / (eta + 1.0);
+    let u: f64 = rng.gen_range(0.0..1.0);
+    let delta = if u < 0.5 {
+        let val = 2.0 * u + (1.0 - 2.0 * u) * (1.0 - delta1).powf(eta + 1.0);
+        val.powf(exp) - 1.0
+    } else {
+        let val = 2.0 * (1.0 - u) + 2.0 * (u - 0.5) * (1.0 - delta2).powf(eta + 1.0);
+        1.0 - val.powf(exp)
+    };
+    (x + delta * width).clamp(v.min, v.max)
+}
+
+// Transformacja Boxa–Mullera: dwie liczby z U(0, 1) → jedna z N(0, 1).
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen_range(0.0..1.0); // (0, 1] – ln(0) byłby -inf
+    let u2 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rng_from_seed, GaRng};
+
+    fn ops(crossover: RealCrossover, mutation: RealMutation) -> RealOperators {
+        RealOperators { crossover, mutation, ..RealOperators::default() }
+    }
+
+    const A: [f64; 3] = [-1.0, 2.0, 0.5];
+    const B: [f64; 3] = [3.0, 2.0, -4.5];
+
+    #[test]
+    fn arithmetic_and_sbx_children_are_symmetric_around_parents() {
+        let mut rng = rng_from_seed(1);
+        for crossover in [RealCrossover::Arithmetic, RealCrossover::Sbx] {
+            for _ in 0..100 {
+                let (ca, cb) = ops(crossover, RealMutation::Gaussian).crossover(&A, &B, &mut rng);
+                for i in 0..A.len() {
+                    assert!((ca[i] + cb[i] - (A[i] + B[i])).abs() < 1e-9, "{crossover:?}");
+                }
+                if crossover == RealCrossover::Arithmetic {
+                    assert!(ca.iter().zip(A.iter().zip(&B)).all(|(c, (x, y))| x.min(*y) <= *c && *c <= x.max(*y)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blx_children_stay_in_widened_interval() {
+        let mut rng = rng_from_seed(2);
+        let blx = ops(RealCrossover::Blx, RealMutation::Gaussian);
+        for _ in 0..200 {
+            let (ca, cb) = blx.crossover(&A, &B, &mut rng);
+            for (i, c) in ca.iter().chain(&cb).enumerate() {
+                let (x, y) = (A[i % 3], B[i % 3]);
+                let d = blx.blx_alpha * (x - y).abs();
+                assert!(x.min(y) - d <= *c && *c <= x.max(y) + d);
+            }
+            // Równi rodzice (gen 2) dają kopie.
+            assert_eq!((ca[1], cb[1]), (2.0, 2.0));
+        }
+    }
+
+    #[test]
+    fn mutation_probability_selects_genes() {
+        let vars = vec![VarSpec::default(); 3];
+        let mut rng = rng_from_seed(3);
+        for mutation in RealMutation::ALL {
+            let mut xs = A.to_vec();
+            assert!(ops(RealCrossover::Blx, mutation).mutate(&mut xs, &vars, 0.0, &mut rng).is_empty());
+            assert_eq!(xs, A);
+            assert_eq!(ops(RealCrossover::Blx, mutation).mutate(&mut xs, &vars, 1.0, &mut rng), [0, 1, 2]);
+            assert_ne!(xs, A);
+        }
+    }
+
+    #[test]
+    fn polynomial_mutation_stays_in_domain() {
+        let vars = [VarSpec { min: 0.0, max: 2.0, bits: 16 }];
+        let mut rng = rng_from_seed(4);
+        let mean_step = |eta: f64, rng: &mut GaRng| {
+            let poly = RealOperators { poly_eta: eta, ..ops(RealCrossover::Blx, RealMutation::Polynomial) };
+            let mut total = 0.0;
+            for start in [0.0, 0.01, 1.0, 1.99, 2.0] {
+                for _ in 0..2000 {
+                    let mut xs = [start];
+                    poly.mutate(&mut xs, &vars, 1.0, rng);
+                    assert!((0.0..=2.0).contains(&xs[0]), "η = {eta}, x = {start} → {}", xs[0]);
+                    total += (xs[0] - start).abs();
+                }
+            }
+            total / 10_000.0
+        };
+        let (wide, narrow) = (mean_step(2.0, &mut rng), mean_step(100.0, &mut rng));
+        assert!(narrow < wide / 3.0, "η = 2: {wide}, η = 100: {narrow}");
+    }
+
+    #[test]
+    fn standard_normal_has_unit_variance() {
+        let mut rng = rng_from_seed(5);
+        let samples: Vec<f64> = (0..20_000).map(|_| standard_normal(&mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!(mean.abs() < 0.05, "mean = {mean}");
+        assert!((var - 1.0).abs() < 0.05, "var = {var}");
+        assert!(samples.iter().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        assert_eq!(RealOperators::default().validate(), Ok(()));
+        let bad = [
+            RealOperators { blx_alpha: -0.1, ..RealOperators::default() },
+            RealOperators { sbx_eta: f64::INFINITY, ..RealOperators::default() },
+            RealOperators { poly_eta: -1.0, ..RealOperators::default() },
+            RealOperators { gauss_sigma: 0.0, ..RealOperators::default() },
+        ];
+        assert!(bad.iter().all(|ops| ops.validate().is_err()));
+    }
+}
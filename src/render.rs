@@ -0,0 +1,353 @@
+// ---------------------------------------------------------------------------
+// Cel rysowania wykresów
+//
+// Funkcje rysujące wykres (tło, siatka, osie, krzywa, populacja, mapa) nie
+// wołają bezpośrednio `egui::Painter`, tylko metod cechy `RenderTarget`.
+// Ten sam wykres trafia więc na ekran (`Painter`), do dokumentu SVG
+// (`SvgTarget`), a przez SVG – do pliku PNG w wybranej skali, bez okna.
+//
+// Do rasteryzacji PNG ładowane są czcionki wbudowane w egui (Hack i Ubuntu),
+// więc obraz wygląda tak samo jak na ekranie także na serwerze bez czcionek.
+// ---------------------------------------------------------------------------
+
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use base64::Engine as _;
+use eframe::egui;
+use egui::{Align, Align2, Color32, FontFamily, FontId, Painter, Pos2, Rect, Stroke, Vec2};
+use resvg::{tiny_skia, usvg};
+
+/// Powierzchnia, na której rysowany jest wykres. Nazwy metod są takie jak
+/// w `egui::Painter`, więc kod rysujący wygląda tak samo dla każdego celu.
+pub trait RenderTarget {
+    fn line_segment(&self, points: [Pos2; 2], stroke: Stroke);
+
+    fn rect_filled(&self, rect: Rect, radius: f32, color: Color32);
+
+    /// Koło z wypełnieniem i obwódką (przezroczyste wypełnienie / `Stroke::NONE` = brak).
+    fn circle(&self, center: Pos2, radius: f32, fill: Color32, stroke: Stroke);
+
+    fn circle_filled(&self, center: Pos2, radius: f32, fill: Color32) {
+        self.circle(center, radius, fill, Stroke::NONE);
+    }
+
+    fn circle_stroke(&self, center: Pos2, radius: f32, stroke: Stroke) {
+        self.circle(center, radius, Color32::TRANSPARENT, stroke);
+    }
+
+    /// Tekst w jednej linii zaczepiony w `pos` punktem `anchor`.
+    fn text(&self, pos: Pos2, anchor: Align2, text: impl ToString, font: FontId, color: Color32);
+
+    /// Rozmiar tekstu – np. na tło pod etykietą.
+    fn text_size(&self, text: &str, font: &FontId) -> Vec2;
+
+    /// Obraz rastrowy rozciągnięty na `rect`.
+    fn image(&self, rect: Rect, image: &RasterImage);
+}
+
+/// Obraz rastrowy (np. mapa kolorów): piksele i – na ekranie – tekstura
+/// tworzona przy pierwszym rysowaniu.
+pub struct RasterImage {
+    pub pixels: egui::ColorImage,
+    texture:    RefCell<Option<egui::TextureHandle>>,
+}
+
+impl RasterImage {
+    pub fn new(pixels: egui::ColorImage) -> Self {
+        Self { pixels, texture: RefCell::new(None) }
+    }
+}
+
+// -- Ekran ---------------------------------------------------------------------
+
+impl RenderTarget for Painter {
+    fn line_segment(&self, points: [Pos2; 2], stroke: Stroke) {
+        Painter::line_segment(self, points, stroke);
+    }
+
+    fn rect_filled(&self, rect: Rect, radius: f32, color: Color32) {
+        Painter::rect_filled(self, rect, radius, color);
+    }
+
+    fn circle(&self, center: Pos2, radius: f32, fill: Color32, stroke: Stroke) {
+        Painter::circle(self, center, radius, fill, stroke);
+    }
+
+    fn text(&self, pos: Pos2, anchor: Align2, text: impl ToString, font: FontId, color: Color32) {
+        Painter::text(self, pos, anchor, text, font, color);
+    }
+
+    fn text_size(&self, text: &str, font: &FontId) -> Vec2 {
+        self.layout_no_wrap(text.to_string(), font.clone(), Color32::WHITE).size()
+    }
+
+    fn image(&self, rect: Rect, image: &RasterImage) {
+        let mut texture = image.texture.borrow_mut();
+        let texture = texture.get_or_insert_with(|| {
+            self.ctx().load_texture("raster", image.pixels.clone(), egui::TextureOptions::LINEAR)
+        });
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        Painter::image(self, texture.id(), rect, uv, Color32::WHITE);
+    }
+}
+
+// -- SVG -----------------------------------------------------------------------
+
+/// Dokument SVG budowany przez funkcje rysujące; wykres zajmuje prostokąt
+/// [`SvgTarget::rect`] o początku w (0, 0).
+pub struct SvgTarget {
+    size: Vec2,
+    body: RefCell<String>,
+}
+
+impl SvgTarget {
+    pub fn new(size: Vec2) -> Self {
+        Self { size, body: RefCell::new(String::new()) }
+    }
+
+    /// Prostokąt, na którym należy rysować.
+    pub fn rect(&self) -> Rect {
+        Rect::from_min_size(Pos2::ZERO, self.size)
+    }
+
+    /// Gotowy dokument SVG.
+    pub fn finish(self) -> String {
+        let (w, h) = (self.size.x, self.size.y);
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{}</svg>\n",
+            self.body.into_inner()
+        )
+    }
+
+    fn push(&self, element: std::fmt::Arguments) {
+        let mut body = self.body.borrow_mut();
+        let _ = body.write_fmt(element);
+        body.push('\n');
+    }
+}
+
+impl RenderTarget for SvgTarget {
+    fn line_segment(&self, [a, b]: [Pos2; 2], stroke: Stroke) {
+        self.push(format_args!(
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" {}/>",
+            a.x, a.y, b.x, b.y, stroke_attrs(stroke)
+        ));
+    }
+
+    fn rect_filled(&self, rect: Rect, radius: f32, color: Color32) {
+        self.push(format_args!(
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" rx=\"{radius}\" {}/>",
+            rect.left(), rect.top(), rect.width(), rect.height(), paint_attrs("fill", color)
+        ));
+    }
+
+    fn circle(&self, center: Pos2, radius: f32, fill: Color32, stroke: Stroke) {
+        self.push(format_args!(
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{radius}\" {} {}/>",
+            center.x, center.y, paint_attrs("fill", fill), stroke_attrs(stroke)
+        ));
+    }
+
+    fn text(&self, pos: Pos2, anchor: Align2, text: impl ToString, font: FontId, color: Color32) {
+        let size = font.size;
+        let text_anchor = match anchor.x() {
+            Align::Min    => "start",
+            Align::Center => "middle",
+            Align::Max    => "end",
+        };
+        // Linia bazowa liczona ręcznie – `dominant-baseline` różne przeglądarki
+        // rozumieją różnie.
+        let baseline = match anchor.y() {
+            Align::Min    => pos.y + size * 0.8,
+            Align::Center => pos.y + size * 0.35,
+            Align::Max    => pos.y - size * 0.2,
+        };
+        let family = match font.family {
+            FontFamily::Monospace => "Hack, monospace",
+            _                     => "Ubuntu, sans-serif",
+        };
+        self.push(format_args!(
+            "<text x=\"{:.2}\" y=\"{baseline:.2}\" font-family=\"{family}\" font-size=\"{size}\" \
+             text-anchor=\"{text_anchor}\" {}>{}</text>",
+            pos.x,
+            paint_attrs("fill", color),
+            escape(&text.to_string())
+        ));
+    }
+
+    fn text_size(&self, text: &str, font: &FontId) -> Vec2 {
+        // Przybliżenie: szerokość znaku Hack to 0,6 wysokości czcionki.
+        let advance = match font.family {
+            FontFamily::Monospace => 0.6,
+            _                     => 0.5,
+        };
+        Vec2::new(text.chars().count() as f32 * font.size * advance, font.size * 1.2)
+    }
+
+    fn image(&self, rect: Rect, image: &RasterImage) {
+        let Some(png) = encode_png(&image.pixels) else { return };
+        self.push(format_args!(
+            "<image x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" preserveAspectRatio=\"none\" \
+             href=\"data:image/png;base64,{}\"/>",
+            rect.left(), rect.top(), rect.width(), rect.height(),
+            base64::engine::general_purpose::STANDARD.encode(png)
+        ));
+    }
+}
+
+// Kolor jako atrybut SVG: "#rrggbb" i ewentualna nieprzezroczystość.
+fn paint_attrs(attr: &str, color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    match a {
+        0   => format!("{attr}=\"none\""),
+        255 => format!("{attr}=\"#{r:02x}{g:02x}{b:02x}\""),
+        _   => format!("{attr}=\"#{r:02x}{g:02x}{b:02x}\" {attr}-opacity=\"{:.3}\"", a as f32 / 255.0),
+    }
+}
+
+fn stroke_attrs(stroke: Stroke) -> String {
+    if stroke.width <= 0.0 {
+        return "stroke=\"none\"".into();
+    }
+    format!("{} stroke-width=\"{}\"", paint_attrs("stroke", stroke.color), stroke.width)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn encode_png(image: &egui::ColorImage) -> Option<Vec<u8>> {
+    let [w, h] = image.size;
+    // Piksele egui i tiny-skia to RGBA z przemnożoną przezroczystością.
+    let data = image.pixels.iter().flat_map(|c| c.to_array()).collect();
+    let size = tiny_skia::IntSize::from_wh(w as u32, h as u32)?;
+    tiny_skia::Pixmap::from_vec(data, size)?.encode_png().ok()
+}
+
+// -- Pliki -----------------------------------------------------------------------
+
+/// Format pliku z wykresem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlotFormat {
+    Svg,
+    Png,
+}
+
+impl PlotFormat {
+    pub const ALL: [PlotFormat; 2] = [PlotFormat::Svg, PlotFormat::Png];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlotFormat::Svg => "SVG",
+            PlotFormat::Png => "PNG",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            PlotFormat::Svg => "svg",
+            PlotFormat::Png => "png",
+        }
+    }
+
+    /// Format wynikający z rozszerzenia pliku.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|f| f.extension() == ext)
+            .ok_or_else(|| format!("{}: nieznany format wykresu (svg | png)", path.display()))
+    }
+}
+
+/// Zapisuje wykres: SVG bez zmian albo PNG powiększony `scale` razy
+/// (np. 2 – dwa razy więcej pikseli w każdym wymiarze niż jednostek SVG).
+pub fn save_plot(path: &Path, format: PlotFormat, svg: &str, scale: f32) -> Result<(), String> {
+    let data = match format {
+        PlotFormat::Svg => svg.as_bytes().to_vec(),
+        PlotFormat::Png => rasterize(svg, scale)?,
+    };
+    std::fs::write(path, data).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Rasteryzuje dokument SVG do PNG.
+pub fn rasterize(svg: &str, scale: f32) -> Result<Vec<u8>, String> {
+    let mut options = usvg::Options::default();
+    let fonts = options.fontdb_mut();
+    for data in egui::FontDefinitions::default().font_data.values() {
+        fonts.load_font_data(data.font.to_vec());
+    }
+    fonts.set_monospace_family("Hack");
+    fonts.set_sans_serif_family("Ubuntu");
+
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|e| format!("niepoprawny SVG: {e}"))?;
+    let size = tree
+        .size()
+        .to_int_size()
+        .scale_by(scale)
+        .ok_or("niepoprawna skala obrazu")?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("za duży obraz")?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|e| format!("nie udało się zapisać PNG: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn svg_with(draw: impl FnOnce(&SvgTarget)) -> String {
+        let target = SvgTarget::new(Vec2::new(40.0, 30.0));
+        draw(&target);
+        target.finish()
+    }
+
+    #[test]
+    fn svg_document_has_plot_size_and_escaped_text() {
+        let svg = svg_with(|t| {
+            t.rect_filled(t.rect(), 0.0, Color32::WHITE);
+            t.text(Pos2::new(20.0, 15.0), Align2::CENTER_CENTER, "x < 1 & y", FontId::monospace(10.0), Color32::BLACK);
+        });
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("width=\"40\" height=\"30\" viewBox=\"0 0 40 30\""));
+        assert!(svg.contains(">x &lt; 1 &amp; y</text>"));
+        assert!(svg.contains("text-anchor=\"middle\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn colors_and_strokes_map_to_svg_attributes() {
+        assert_eq!(paint_attrs("fill", Color32::TRANSPARENT), "fill=\"none\"");
+        assert_eq!(paint_attrs("fill", Color32::from_rgb(255, 0, 16)), "fill=\"#ff0010\"");
+        assert!(paint_attrs("fill", Color32::from_rgba_unmultiplied(0, 0, 0, 51)).contains("fill-opacity=\"0.200\""));
+        assert_eq!(stroke_attrs(Stroke::NONE), "stroke=\"none\"");
+        assert_eq!(stroke_attrs(Stroke::new(1.5, Color32::BLACK)), "stroke=\"#000000\" stroke-width=\"1.5\"");
+    }
+
+    #[test]
+    fn raster_images_are_embedded_as_png() {
+        let image = RasterImage::new(egui::ColorImage::new([2, 2], vec![Color32::RED; 4]));
+        let svg = svg_with(|t| t.image(t.rect(), &image));
+        assert!(svg.contains("href=\"data:image/png;base64,"));
+    }
+
+    #[test]
+    fn png_is_scaled() {
+        let svg = svg_with(|t| {
+            t.circle(Pos2::new(20.0, 15.0), 5.0, Color32::RED, Stroke::new(1.0, Color32::BLACK));
+            t.line_segment([Pos2::ZERO, Pos2::new(40.0, 30.0)], Stroke::new(1.0, Color32::BLUE));
+        });
+        let png = rasterize(&svg, 2.0).unwrap();
+        let pixmap = tiny_skia::Pixmap::decode_png(&png).unwrap();
+        assert_eq!((pixmap.width(), pixmap.height()), (80, 60));
+        assert!(rasterize("nie svg", 1.0).is_err());
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(PlotFormat::from_path(Path::new("wykres.SVG")), Ok(PlotFormat::Svg));
+        assert_eq!(PlotFormat::from_path(Path::new("wykres.png")), Ok(PlotFormat::Png));
+        assert!(PlotFormat::from_path(Path::new("wykres.jpg")).is_err());
+    }
+}
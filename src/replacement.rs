@@ -0,0 +1,171 @@
+// ---------------------------------------------------------------------------
+// Zastępowanie (selekcja przeżywających)
+//
+// Decyduje, kto z rodziców (μ = rozmiar populacji) i dzieci (λ) trafi do
+// następnego pokolenia:
+//   - pokoleniowe  – dzieci zastępują całą populację, poza elitą,
+//   - (μ+λ)        – μ najlepszych spośród rodziców i λ dzieci razem,
+//   - (μ,λ)        – μ najlepszych spośród samych λ dzieci (plus elita),
+//   - stacjonarne  – w każdym kroku tylko kilkoro dzieci zastępuje
+//                    najgorszych osobników, reszta populacji zostaje.
+// Samo zastępowanie wykonuje silnik ([`crate::Engine::step`]).
+// ---------------------------------------------------------------------------
+
+use serde::{Deserialize, Serialize};
+
+/// Strategia zastępowania populacji.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Replacement {
+    #[default]
+    Generational,
+    MuPlusLambda,
+    MuCommaLambda,
+    SteadyState,
+}
+
+impl Replacement {
+    pub const ALL: [Replacement; 4] = [
+        Replacement::Generational,
+        Replacement::MuPlusLambda,
+        Replacement::MuCommaLambda,
+        Replacement::SteadyState,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Replacement::Generational  => "Pokoleniowe",
+            Replacement::MuPlusLambda  => "(μ+λ)",
+            Replacement::MuCommaLambda => "(μ,λ)",
+            Replacement::SteadyState   => "Stacjonarne",
+        }
+    }
+}
+
+/// Strategia zastępowania wraz z parametrami.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplacementParams {
+    pub strategy:     Replacement,
+    /// Liczba dzieci λ na pokolenie w strategiach (μ+λ) i (μ,λ).
+    pub lambda:       usize,
+    /// Liczba dzieci zastępujących najgorszych w jednym kroku stacjonarnym.
+    pub steady_count: usize,
+}
+
+impl Default for ReplacementParams {
+    fn default() -> Self {
+        Self {
+            strategy:     Replacement::Generational,
+            lambda:       40,
+            steady_count: 2,
+        }
+    }
+}
+
+impl ReplacementParams {
+    /// Sprawdza parametry względem rozmiaru populacji μ i liczby elit;
+    /// zwraca opis pierwszego błędu.
+    pub fn validate(&self, pop_size: usize, elitism: usize) -> Result<(), String> {
+        match self.strategy {
+            Replacement::Generational => {}
+            Replacement::MuPlusLambda => {
+                if self.lambda < 1 {
+                    return Err("λ musi wynosić co najmniej 1".into());
+                }
+            }
+            Replacement::MuCommaLambda => {
+                if self.lambda < pop_size - elitism {
+                    return Err("w strategii (μ,λ) λ nie może być mniejsze od μ minus liczba elit".into());
+                }
+            }
+            Replacement::SteadyState => {
+                if !(1..=pop_size - elitism).contains(&self.steady_count) {
+                    return Err("liczba zastępowanych osobników musi należeć do [1, μ − elity]".into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{objective, rng_from_seed, Engine, OptionsParams, Population};
+
+    fn params(strategy: Replacement, elitism: usize) -> OptionsParams {
+        OptionsParams {
+            pop_size: 20,
+            elitism,
+            replacement: ReplacementParams { strategy, lambda: 30, steady_count: 3 },
+            ..OptionsParams::default()
+        }
+    }
+
+    /// Populacje kolejnych pokoleń przebiegu (z populacją startową).
+    fn run(params: &OptionsParams, generations: usize) -> Vec<Population> {
+        let engine = Engine::new(objective::default_objective());
+        let mut rng = rng_from_seed(7);
+        let mut pop = engine.random_population(params, &mut rng);
+        let mut pops = vec![pop.clone()];
+        for _ in 0..generations {
+            engine.step(&mut pop, params, &mut rng);
+            pops.push(pop.clone());
+        }
+        pops
+    }
+
+    fn best(pop: &Population) -> f64 {
+        pop.best().unwrap().fitness
+    }
+
+    #[test]
+    fn every_strategy_keeps_population_size() {
+        for strategy in Replacement::ALL {
+            for elitism in [0, 2] {
+                let params = params(strategy, elitism);
+                assert_eq!(params.validate(), Ok(()));
+                for pop in run(&params, 10) {
+                    assert_eq!(pop.chromosomes.len(), params.pop_size, "{strategy:?}, elity: {elitism}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mu_plus_lambda_never_loses_the_best() {
+        let pops = run(&params(Replacement::MuPlusLambda, 0), 20);
+        assert!(pops.windows(2).all(|w| best(&w[1]) >= best(&w[0])));
+    }
+
+    #[test]
+    fn elitism_keeps_the_best_in_generational_and_comma_strategies() {
+        for strategy in [Replacement::Generational, Replacement::MuCommaLambda] {
+            let pops = run(&params(strategy, 1), 20);
+            assert!(pops.windows(2).all(|w| best(&w[1]) >= best(&w[0])), "{strategy:?}");
+        }
+    }
+
+    #[test]
+    fn steady_state_replaces_only_a_few_individuals() {
+        let pops = run(&params(Replacement::SteadyState, 0), 10);
+        for w in pops.windows(2) {
+            let born = w[1].chromosomes.iter().filter(|c| c.origin.birth == w[1].generation).count();
+            assert!(born <= 3);
+            let kept = w[1].chromosomes.iter().filter(|c| w[0].chromosomes.iter().any(|p| p.origin.id == c.origin.id));
+            assert!(kept.count() >= 17);
+        }
+    }
+
+    #[test]
+    fn parameters_are_checked_against_population_size() {
+        let comma = ReplacementParams { strategy: Replacement::MuCommaLambda, lambda: 18, steady_count: 1 };
+        assert!(comma.validate(20, 2).is_ok());
+        assert!(comma.validate(20, 1).is_err());
+        let plus = ReplacementParams { strategy: Replacement::MuPlusLambda, lambda: 0, steady_count: 1 };
+        assert!(plus.validate(20, 0).is_err());
+        let steady = ReplacementParams { strategy: Replacement::SteadyState, lambda: 1, steady_count: 19 };
+        assert!(steady.validate(20, 1).is_ok());
+        assert!(steady.validate(20, 2).is_err());
+    }
+}
@@ -0,0 +1,416 @@
+// ---------------------------------------------------------------------------
+// Selekcja rodziców
+//
+// Każda metoda selekcji implementuje [`Selector`]: z posortowanej populacji
+// (najlepszy pierwszy) wybiera pulę rodziców, z której silnik bierze kolejne
+// pary do krzyżowania. Pula wybierana jest od razu w całości – tego wymaga
+// losowanie stochastyczne uniwersalne (SUS), które rozstawia wszystkie
+// "wskaźniki" na kole ruletki jednym losowaniem.
+// ---------------------------------------------------------------------------
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::Chromosome;
+
+/// Jedno losowanie selekcji: indeks wybranego osobnika w populacji oraz,
+/// dla turnieju, indeksy wszystkich uczestników (tryb nauki je pokazuje).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Draw {
+    pub winner:      usize,
+    pub competitors: Vec<usize>,
+}
+
+impl Draw {
+    fn of(winner: usize) -> Self {
+        Self { winner, competitors: Vec::new() }
+    }
+}
+
+/// Wspólny interfejs metod selekcji.
+pub trait Selector {
+    /// Losuje `count` rodziców (z powtórzeniami) z populacji posortowanej
+    /// malejąco po fitness.
+    fn draw(&self, pop: &[Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<Draw>;
+
+    /// Jak [`Selector::draw`], ale zwraca od razu wybrane chromosomy.
+    fn select<'a>(&self, pop: &'a [Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<&'a Chromosome> {
+        self.draw(pop, count, rng).into_iter().map(|d| &pop[d.winner]).collect()
+    }
+}
+
+/// Metoda selekcji wybierana w oknie opcji.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionMethod {
+    #[default]
+    Tournament,
+    Roulette,
+    Rank,
+    Sus,
+    Truncation,
+    Boltzmann,
+}
+
+impl SelectionMethod {
+    pub const ALL: [SelectionMethod; 6] = [
+        SelectionMethod::Tournament,
+        SelectionMethod::Roulette,
+        SelectionMethod::Rank,
+        SelectionMethod::Sus,
+        SelectionMethod::Truncation,
+        SelectionMethod::Boltzmann,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SelectionMethod::Tournament => "Turniejowa",
+            SelectionMethod::Roulette   => "Ruletka",
+            SelectionMethod::Rank       => "Rankingowa liniowa",
+            SelectionMethod::Sus        => "Stochastyczna uniwersalna (SUS)",
+            SelectionMethod::Truncation => "Obcięciowa",
+            SelectionMethod::Boltzmann  => "Boltzmanna",
+        }
+    }
+}
+
+/// Metoda selekcji wraz z parametrami metod innych niż turniejowa
+/// (rozmiar turnieju to osobny parametr [`crate::OptionsParams::tournament_k`]).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelectionParams {
+    pub method:        SelectionMethod,
+    /// Nacisk selekcyjny s ∈ [1, 2] selekcji rankingowej
+    /// (1 = wszyscy równi, 2 = najgorszy nie ma szans).
+    pub rank_pressure: f64,
+    /// Ułamek najlepszych osobników, spośród których losuje selekcja obcięciowa.
+    pub truncation:    f64,
+    /// Temperatura T selekcji Boltzmanna – im niższa, tym silniejsza presja.
+    pub temperature:   f64,
+}
+
+impl Default for SelectionParams {
+    fn default() -> Self {
+        Self {
+            method:        SelectionMethod::Tournament,
+            rank_pressure: 1.5,
+            truncation:    0.5,
+            temperature:   1.0,
+        }
+    }
+}
+
+impl SelectionParams {
+    /// Sprawdza parametry selekcji; zwraca opis pierwszego błędu.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1.0..=2.0).contains(&self.rank_pressure) {
+            return Err("nacisk selekcji rankingowej musi należeć do [1, 2]".into());
+        }
+        if !(self.truncation > 0.0 && self.truncation <= 1.0) {
+            return Err("ułamek selekcji obcięciowej musi należeć do (0, 1]".into());
+        }
+        if !(self.temperature > 0.0 && self.temperature.is_finite()) {
+            return Err("temperatura selekcji Boltzmanna musi być dodatnia".into());
+        }
+        Ok(())
+    }
+
+    /// Tworzy obiekt selekcji dla wybranej metody.
+    pub fn selector(&self, tournament_k: usize) -> Box<dyn Selector> {
+        match self.method {
+            SelectionMethod::Tournament => Box::new(Tournament { k: tournament_k }),
+            SelectionMethod::Roulette   => Box::new(Roulette),
+            SelectionMethod::Rank       => Box::new(LinearRank { pressure: self.rank_pressure }),
+            SelectionMethod::Sus        => Box::new(StochasticUniversal),
+            SelectionMethod::Truncation => Box::new(Truncation { ratio: self.truncation }),
+            SelectionMethod::Boltzmann  => Box::new(Boltzmann { temperature: self.temperature }),
+        }
+    }
+}
+
+// -- Selekcja turniejowa -----------------------------------------------------
+// Losujemy K osobników, wygrywa ten z najwyższym fitness.
+// Wyobraź sobie turniej: losowo wybierasz K zawodników ze starej
+// populacji i przepuszczasz najlepszego dalej. Powtarzasz tyle razy,
+// ile potrzebujesz rodziców.
+pub struct Tournament {
+    pub k: usize,
+}
+
+impl Selector for Tournament {
+    fn draw(&self, pop: &[Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<Draw> {
+        (0..count)
+            .map(|_| {
+                let mut best_idx = rng.gen_range(0..pop.len());
+                let mut competitors = vec![best_idx];
+                for _ in 1..self.k {
+                    let idx = rng.gen_range(0..pop.len());
+                    competitors.push(idx);
+                    if pop[idx].fitness > pop[best_idx].fitness {
+                        best_idx = idx;
+                    }
+                }
+                Draw { winner: best_idx, competitors }
+            })
+            .collect()
+    }
+}
+
+// -- Ruletka -----------------------------------------------------------------
+// Szansa wyboru proporcjonalna do fitness. Fitness może być ujemny
+// (np. przy minimalizacji bez przekształcenia), więc wagi liczymy od
+// najgorszego osobnika: w = fitness − fitness_min. Najgorszy dostaje
+// wagę 0, a gdy wszyscy są równi – losujemy jednostajnie.
+pub struct Roulette;
+
+impl Selector for Roulette {
+    fn draw(&self, pop: &[Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<Draw> {
+        let weights = shifted_weights(pop);
+        (0..count).map(|_| Draw::of(spin(&weights, rng))).collect()
+    }
+}
+
+// -- Selekcja rankingowa liniowa ---------------------------------------------
+// Liczy się tylko miejsce w rankingu, nie sama wartość fitness – jeden
+// "superosobnik" nie zdominuje populacji. Przy nacisku s najlepszy dostaje
+// wagę s, najgorszy 2 − s, a pozostali liniowo pomiędzy.
+pub struct LinearRank {
+    pub pressure: f64,
+}
+
+impl Selector for LinearRank {
+    fn draw(&self, pop: &[Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<Draw> {
+        let n = pop.len();
+        let s = self.pressure;
+        // Populacja jest posortowana malejąco, więc indeks i ma rangę n − 1 − i
+        // (0 = najgorszy).
+        let weights: Vec<f64> = (0..n)
+            .map(|i| {
+                let rank = (n - 1 - i) as f64;
+                let span = (n - 1).max(1) as f64;
+                (2.0 - s) + 2.0 * (s - 1.0) * rank / span
+            })
+            .collect();
+        (0..count).map(|_| Draw::of(spin(&weights, rng))).collect()
+    }
+}
+
+// -- Losowanie stochastyczne uniwersalne (SUS) -------------------------------
+// Ruletka z `count` równo rozstawionymi wskaźnikami i jednym obrotem koła.
+// Każdy osobnik dostaje liczbę kopii bliską oczekiwanej – bez "pecha"
+// wielokrotnych, niezależnych obrotów zwykłej ruletki.
+pub struct StochasticUniversal;
+
+impl Selector for StochasticUniversal {
+    fn draw(&self, pop: &[Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<Draw> {
+        if count == 0 {
+            return Vec::new();
+        }
+        let weights = shifted_weights(pop);
+        let total: f64 = weights.iter().sum();
+        if !(total > 0.0 && total.is_finite()) {
+            // Wszyscy równi – jak w `spin`, wybór jednostajny.
+            return (0..count).map(|_| Draw::of(rng.gen_range(0..pop.len()))).collect();
+        }
+        let step = total / count as f64;
+        let start = rng.gen_range(0.0..step);
+
+        let mut chosen = Vec::with_capacity(count);
+        let mut idx = 0;
+        let mut cumulative = weights[0];
+        for p in 0..count {
+            let pointer = start + p as f64 * step;
+            while cumulative < pointer && idx + 1 < weights.len() {
+                idx += 1;
+                cumulative += weights[idx];
+            }
+            chosen.push(Draw::of(idx));
+        }
+        // Wskaźniki idą po kolei, więc bez tasowania pary rodziców byłyby
+        // zawsze sąsiadami w rankingu.
+        chosen.shuffle(rng);
+        chosen
+    }
+}
+
+// -- Selekcja obcięciowa -----------------------------------------------------
+// Odrzucamy wszystkich poza najlepszym ułamkiem populacji, a spośród
+// pozostałych losujemy jednostajnie.
+pub struct Truncation {
+    pub ratio: f64,
+}
+
+impl Selector for Truncation {
+    fn draw(&self, pop: &[Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<Draw> {
+        let keep = ((pop.len() as f64 * self.ratio).ceil() as usize).clamp(1, pop.len());
+        (0..count).map(|_| Draw::of(rng.gen_range(0..keep))).collect()
+    }
+}
+
+// -- Selekcja Boltzmanna -----------------------------------------------------
+// Waga exp(fitness / T): przy wysokiej temperaturze wybór jest prawie
+// jednostajny, przy niskiej – prawie zawsze wygrywa najlepszy.
+// Od fitness odejmujemy maksimum, żeby exp() się nie przepełnił.
+pub struct Boltzmann {
+    pub temperature: f64,
+}
+
+impl Boltzmann {
+    /// Wagi osobników: najlepszy ma 1, nieskończony/NaN fitness – 0.
+    fn weights(&self, pop: &[Chromosome]) -> Vec<f64> {
+        let f_max = finite_fitness(pop).fold(f64::NEG_INFINITY, f64::max);
+        pop.iter()
+            .map(|c| {
+                if c.fitness.is_finite() {
+                    ((c.fitness - f_max) / self.temperature).exp()
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+}
+
+impl Selector for Boltzmann {
+    fn draw(&self, pop: &[Chromosome], count: usize, rng: &mut dyn RngCore) -> Vec<Draw> {
+        let weights = self.weights(pop);
+        (0..count).map(|_| Draw::of(spin(&weights, rng))).collect()
+    }
+}
+
+fn finite_fitness(pop: &[Chromosome]) -> impl Iterator<Item = f64> + '_ {
+    pop.iter().map(|c| c.fitness).filter(|f| f.is_finite())
+}
+
+// Wagi ruletki: fitness przesunięty tak, by najgorszy skończony miał 0.
+// Osobniki z nieskończonym/NaN fitness nie mają szans.
+fn shifted_weights(pop: &[Chromosome]) -> Vec<f64> {
+    let f_min = finite_fitness(pop).fold(f64::INFINITY, f64::min);
+    pop.iter()
+        .map(|c| if c.fitness.is_finite() { c.fitness - f_min } else { 0.0 })
+        .collect()
+}
+
+// Jeden obrót koła ruletki o podanych wagach. Gdy wszystkie wagi są zerowe
+// (np. cała populacja ma ten sam fitness) – wybór jednostajny.
+fn spin(weights: &[f64], rng: &mut dyn RngCore) -> usize {
+    let total: f64 = weights.iter().sum();
+    if !(total > 0.0 && total.is_finite()) {
+        return rng.gen_range(0..weights.len());
+    }
+    let mut target = rng.gen_range(0.0..total);
+    for (i, w) in weights.iter().enumerate() {
+        if target < *w {
+            return i;
+        }
+        target -= w;
+    }
+    // Błędy zaokrągleń – ostatni osobnik o niezerowej wadze.
+    weights.iter().rposition(|&w| w > 0.0).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromosome::VarSpec;
+    use crate::rng_from_seed;
+
+    /// Populacja o podanych fitness (już posortowanych malejąco).
+    fn pop(fitness: &[f64]) -> Vec<Chromosome> {
+        let vars = [VarSpec::default()];
+        fitness.iter().map(|&fitness| Chromosome { fitness, ..Chromosome::from_xs(vec![0.0], &vars) }).collect()
+    }
+
+    fn all_selectors() -> Vec<Box<dyn Selector>> {
+        SelectionMethod::ALL
+            .iter()
+            .map(|&method| SelectionParams { method, ..SelectionParams::default() }.selector(3))
+            .collect()
+    }
+
+    #[test]
+    fn every_method_returns_count_draws_in_range() {
+        let population = pop(&[5.0, 3.0, 3.0, -1.0, -7.0]);
+        let mut rng = rng_from_seed(1);
+        for selector in all_selectors() {
+            for count in [0, 1, 5, 12] {
+                let draws = selector.draw(&population, count, &mut rng);
+                assert_eq!(draws.len(), count);
+                assert!(draws.iter().all(|d| d.winner < population.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn proportional_methods_skip_worst_and_infinite_fitness() {
+        // Najgorszy skończony ma wagę 0, a -inf nie ma szans.
+        let population = pop(&[-1.0, -2.0, -3.0, f64::NEG_INFINITY]);
+        let mut rng = rng_from_seed(2);
+        for selector in [&Roulette as &dyn Selector, &StochasticUniversal] {
+            let draws = selector.draw(&population, 200, &mut rng);
+            assert!(draws.iter().all(|d| d.winner < 2));
+            assert!(draws.iter().any(|d| d.winner == 1));
+        }
+    }
+
+    #[test]
+    fn proportional_methods_fall_back_to_uniform() {
+        let mut rng = rng_from_seed(3);
+        for fitness in [[2.0; 4], [f64::NEG_INFINITY; 4]] {
+            let population = pop(&fitness);
+            for selector in [&Roulette as &dyn Selector, &StochasticUniversal] {
+                let draws = selector.draw(&population, 200, &mut rng);
+                assert!((0..4).all(|i| draws.iter().any(|d| d.winner == i)));
+            }
+        }
+    }
+
+    #[test]
+    fn sus_copies_match_expected_counts() {
+        // Wagi po przesunięciu: 3, 1, 0 – z 8 wskaźników 6 trafia w pierwszego.
+        let population = pop(&[4.0, 2.0, 1.0]);
+        let draws = StochasticUniversal.draw(&population, 8, &mut rng_from_seed(4));
+        assert_eq!(draws.iter().filter(|d| d.winner == 0).count(), 6);
+        assert_eq!(draws.iter().filter(|d| d.winner == 1).count(), 2);
+    }
+
+    #[test]
+    fn truncation_draws_only_from_the_best_fraction() {
+        let population = pop(&[9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0]);
+        let selector = Truncation { ratio: 0.25 };
+        let draws = selector.draw(&population, 100, &mut rng_from_seed(5));
+        assert!(draws.iter().all(|d| d.winner < 2));
+        let tiny = Truncation { ratio: 1e-9 }.draw(&population, 10, &mut rng_from_seed(5));
+        assert!(tiny.iter().all(|d| d.winner == 0));
+    }
+
+    #[test]
+    fn rank_pressure_two_never_picks_the_worst() {
+        let population = pop(&[1.0, 0.5, 0.25, 0.0]);
+        let draws = LinearRank { pressure: 2.0 }.draw(&population, 300, &mut rng_from_seed(6));
+        assert!(draws.iter().all(|d| d.winner < 3));
+        let single = LinearRank { pressure: 2.0 }.draw(&pop(&[1.0]), 3, &mut rng_from_seed(6));
+        assert!(single.iter().all(|d| d.winner == 0));
+    }
+
+    #[test]
+    fn boltzmann_weights_stay_finite_near_zero_temperature() {
+        let population = pop(&[1.0, 1.0 - 1e-12, -5.0, f64::NEG_INFINITY, f64::NAN]);
+        for temperature in [1e-300, f64::MIN_POSITIVE, 5e-324, 1e300] {
+            let weights = Boltzmann { temperature }.weights(&population);
+            assert!(weights.iter().all(|w| w.is_finite() && *w >= 0.0), "T = {temperature}: {weights:?}");
+            assert_eq!(weights[0], 1.0);
+        }
+        let draws = Boltzmann { temperature: 1e-300 }.draw(&population, 50, &mut rng_from_seed(7));
+        assert!(draws.iter().all(|d| d.winner == 0));
+    }
+
+    #[test]
+    fn tournament_reports_competitors_and_picks_the_best() {
+        let population = pop(&[3.0, 2.0, 1.0]);
+        for draw in (Tournament { k: 4 }).draw(&population, 50, &mut rng_from_seed(8)) {
+            assert_eq!(draw.competitors.len(), 4);
+            assert_eq!(draw.winner, *draw.competitors.iter().min().unwrap());
+        }
+    }
+}
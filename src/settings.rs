@@ -0,0 +1,196 @@
+// ---------------------------------------------------------------------------
+// Ustawienia zapamiętywane między uruchomieniami
+//
+// Ostatnie parametry GA (z dziedziną i ziarnem), funkcja celu, motyw,
+// szerokość panelu populacji oraz rozmiar i położenie okna. Plik TOML
+// w katalogu konfiguracji użytkownika:
+//   $XDG_CONFIG_HOME/genetictool2/settings.toml (domyślnie ~/.config/...),
+//   w Windows %APPDATA%\genetictool2\settings.toml.
+// Zapisywany przy zamykaniu okna. Brakujące pola przyjmują wartości
+// domyślne, więc plik ze starszej wersji programu nadal się wczytuje.
+// ---------------------------------------------------------------------------
+
+use std::path::PathBuf;
+
+use eframe::egui;
+use genetictool2::{objective, Objective, OptionsParams};
+use serde::{Deserialize, Serialize};
+
+/// Motyw kolorystyczny interfejsu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Jak w systemie (jasny albo ciemny).
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::System => "Systemowy",
+            Theme::Light  => "Jasny",
+            Theme::Dark   => "Ciemny",
+        }
+    }
+
+    pub fn preference(self) -> egui::ThemePreference {
+        match self {
+            Theme::System => egui::ThemePreference::System,
+            Theme::Light  => egui::ThemePreference::Light,
+            Theme::Dark   => egui::ThemePreference::Dark,
+        }
+    }
+}
+
+/// Ustawienia programu zapisywane w pliku konfiguracji.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Tekst wyrażenia funkcji celu.
+    pub objective:   String,
+    pub theme:       Theme,
+    /// Szerokość prawego panelu (populacja) w punktach.
+    pub panel_width: f32,
+    /// Rozmiar wnętrza okna w punktach.
+    pub window_size: [f32; 2],
+    /// Położenie okna na ekranie (nieznane np. pod Waylandem).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_pos:  Option<[f32; 2]>,
+    /// Parametry GA, w tym dziedzina (`vars`) i ziarno.
+    pub params:      OptionsParams,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            objective:   objective::DEFAULT_SOURCE.to_string(),
+            theme:       Theme::System,
+            panel_width: 340.0,
+            window_size: [1000.0, 580.0],
+            window_pos:  None,
+            params:      OptionsParams::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Ścieżka pliku ustawień (`None`, gdy nie da się ustalić katalogu domowego).
+    pub fn path() -> Option<PathBuf> {
+        let var = |name| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+        let dir = if cfg!(windows) {
+            var("APPDATA")
+        } else {
+            var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))
+        };
+        Some(dir?.join("genetictool2").join("settings.toml"))
+    }
+
+    /// Wczytuje ustawienia; brak pliku daje ustawienia domyślne.
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = Self::path() else { return Ok(Self::default()) };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        let settings: Self = toml::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        settings.validate().map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(settings)
+    }
+
+    /// Zapisuje ustawienia, tworząc w razie potrzeby katalog konfiguracji.
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("nie znaleziono katalogu konfiguracji")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        }
+        let text = toml::to_string(self).map_err(|e| format!("nie udało się zapisać ustawień: {e}"))?;
+        std::fs::write(&path, text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Funkcja celu z ustawień (poprawność sprawdza [`Settings::load`]).
+    pub fn objective(&self) -> Objective {
+        Objective::parse(&self.objective).unwrap_or_else(|_| objective::default_objective())
+    }
+
+    /// Sprawdza parametry, zgodność funkcji celu z dziedziną i wymiary okna.
+    fn validate(&self) -> Result<(), String> {
+        self.params.validate()?;
+        let objective = Objective::parse(&self.objective).map_err(|e| format!("funkcja celu: {}", e.msg))?;
+        if objective.dims() != self.params.vars.len() {
+            return Err("liczba zmiennych funkcji celu nie zgadza się z dziedziną".into());
+        }
+        let sizes = [self.panel_width, self.window_size[0], self.window_size[1]];
+        if !sizes.iter().all(|s| s.is_finite() && (50.0..=20_000.0).contains(s)) {
+            return Err("niepoprawny rozmiar okna albo panelu".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use genetictool2::Direction;
+
+    fn parse(text: &str) -> Result<Settings, String> {
+        let settings: Settings = toml::from_str(text).map_err(|e| e.to_string())?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    #[test]
+    fn defaults_survive_a_round_trip() {
+        let mut settings = Settings { theme: Theme::Dark, window_pos: Some([10.0, 20.0]), ..Settings::default() };
+        settings.params.seed = 123;
+        settings.params.vars[0].max = 4.0;
+        let loaded = parse(&toml::to_string(&settings).unwrap()).unwrap();
+        assert_eq!(loaded.theme, Theme::Dark);
+        assert_eq!(loaded.window_pos, Some([10.0, 20.0]));
+        assert_eq!(loaded.objective, objective::DEFAULT_SOURCE);
+        assert_eq!((loaded.params.seed, &loaded.params.vars), (123, &settings.params.vars));
+    }
+
+    #[test]
+    fn missing_fields_take_default_values() {
+        let loaded = parse("theme = \"Light\"\n").unwrap();
+        assert_eq!(loaded.theme, Theme::Light);
+        assert_eq!(loaded.panel_width, Settings::default().panel_width);
+        assert_eq!(loaded.window_pos, None);
+
+        // Niepełna tabela parametrów (np. z wersji sprzed nowych pól) – reszta
+        // przyjmuje wartości domyślne zamiast odrzucać cały plik.
+        let loaded = parse("theme = \"Dark\"\n[params]\nseed = 77\n[params.fitness]\ndirection = \"Minimize\"\n").unwrap();
+        let defaults = OptionsParams::default();
+        assert_eq!(loaded.theme, Theme::Dark);
+        assert_eq!(loaded.params.seed, 77);
+        assert_eq!(loaded.params.fitness.direction, Direction::Minimize);
+        assert_eq!(loaded.params.fitness.transform, defaults.fitness.transform);
+        assert_eq!((loaded.params.pop_size, &loaded.params.vars), (defaults.pop_size, &defaults.vars));
+        assert_eq!(loaded.params.stop, defaults.stop);
+        assert_eq!(loaded.params.real, defaults.real);
+    }
+
+    #[test]
+    fn inconsistent_settings_are_rejected() {
+        assert!(parse("objective = \"x + y\"\n").unwrap_err().contains("liczba zmiennych"));
+        assert!(parse("objective = \"x +\"\n").unwrap_err().contains("funkcja celu"));
+        assert!(parse("panel_width = 1.0\n").is_err());
+        assert!(parse("window_size = [800.0, nan]\n").is_err());
+        let mut settings = Settings::default();
+        settings.params.pop_size = 1;
+        assert!(parse(&toml::to_string(&settings).unwrap()).is_err());
+    }
+
+    #[test]
+    fn objective_matches_its_source() {
+        let settings = Settings { objective: "sin(x)".into(), ..Settings::default() };
+        assert_eq!(settings.objective().label(), "sin(x)");
+        let broken = Settings { objective: "sin(".into(), ..Settings::default() };
+        assert_eq!(broken.objective().label(), objective::DEFAULT_SOURCE);
+    }
+}
@@ -0,0 +1,115 @@
+// ---------------------------------------------------------------------------
+// Statystyki pokoleń
+//
+// Podsumowanie wartości funkcji celu i różnorodności jednego pokolenia.
+// Historia takich podsumowań (jedno na pokolenie) to wykres zbieżności:
+// widać na nim, czy najlepszy wynik jeszcze się poprawia i jak szybko
+// populacja "dogania" najlepszego osobnika – a po spadku różnorodności,
+// czy nie zbiegła się przedwcześnie.
+// ---------------------------------------------------------------------------
+
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::VarSpec;
+use crate::population::Population;
+
+/// Statystyki wartości funkcji celu f(x) i różnorodności jednego pokolenia.
+///
+/// "Najlepszy" i "najgorszy" w sensie fitness – przy minimalizacji najlepszy
+/// ma najmniejsze f. Wartości nieskończone i NaN są pomijane.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub best:       f64,
+    pub mean:       f64,
+    pub median:     f64,
+    pub worst:      f64,
+    /// Odchylenie standardowe f w populacji.
+    pub std_dev:    f64,
+    /// Średnia odległość Hamminga par (ułamek genomu; NaN dla liczb rzeczywistych).
+    pub hamming:    f64,
+    /// Rozrzut x (patrz `Population::spread`).
+    pub spread:     f64,
+    /// Liczba różnych genotypów.
+    pub unique:     usize,
+}
+
+impl GenerationStats {
+    /// Liczy statystyki populacji o dziedzinie `vars` (NaN, gdy brak
+    /// skończonych wartości f).
+    pub fn of(pop: &Population, vars: &[VarSpec]) -> Self {
+        // Populacja jest posortowana po fitness, więc pierwszy/ostatni
+        // skończony osobnik to najlepszy/najgorszy.
+        let mut finite = pop.chromosomes.iter().map(|c| c.value).filter(|v| v.is_finite());
+        let best  = finite.next().unwrap_or(f64::NAN);
+        let worst = finite.next_back().unwrap_or(best);
+
+        let mut values: Vec<f64> = pop.chromosomes.iter().map(|c| c.value).filter(|v| v.is_finite()).collect();
+        values.sort_by(f64::total_cmp);
+        let n = values.len() as f64;
+        let mean   = values.iter().sum::<f64>() / n;
+        let var    = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        let median = match values.len() {
+            0 => f64::NAN,
+            len if len % 2 == 1 => values[len / 2],
+            len => 0.5 * (values[len / 2 - 1] + values[len / 2]),
+        };
+
+        Self {
+            generation: pop.generation,
+            best,
+            mean,
+            median,
+            worst,
+            std_dev: var.sqrt(),
+            hamming: pop.mean_hamming(),
+            spread:  pop.spread(vars),
+            unique:  pop.unique_genotypes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromosome::Chromosome;
+    use crate::fitness::{Direction, FitnessMode};
+
+    fn pop(values: &[f64], mode: &FitnessMode) -> Population {
+        let vars = [VarSpec::default()];
+        let chromosomes = values
+            .iter()
+            .map(|&value| Chromosome { value, ..Chromosome::from_xs(vec![0.0], &vars) })
+            .collect();
+        Population::from_chromosomes(chromosomes, 4, mode)
+    }
+
+    #[test]
+    fn summary_of_values() {
+        let stats = GenerationStats::of(&pop(&[1.0, 4.0, 2.0, 5.0], &FitnessMode::default()), &[VarSpec::default()]);
+        assert_eq!(stats.generation, 4);
+        assert_eq!((stats.best, stats.worst), (5.0, 1.0));
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.std_dev, 2.5_f64.sqrt());
+    }
+
+    #[test]
+    fn best_and_worst_follow_direction() {
+        let mode = FitnessMode { direction: Direction::Minimize, ..FitnessMode::default() };
+        let stats = GenerationStats::of(&pop(&[1.0, 4.0, 2.0], &mode), &[VarSpec::default()]);
+        assert_eq!((stats.best, stats.worst), (1.0, 4.0));
+        assert_eq!(stats.median, 2.0);
+    }
+
+    #[test]
+    fn non_finite_values_are_skipped() {
+        let values = [f64::NAN, 3.0, f64::INFINITY, 1.0, f64::NEG_INFINITY];
+        let stats = GenerationStats::of(&pop(&values, &FitnessMode::default()), &[VarSpec::default()]);
+        assert_eq!((stats.best, stats.worst), (3.0, 1.0));
+        assert_eq!((stats.mean, stats.median), (2.0, 2.0));
+
+        let stats = GenerationStats::of(&pop(&[f64::NAN, f64::INFINITY], &FitnessMode::default()), &[]);
+        assert!(stats.best.is_nan() && stats.worst.is_nan() && stats.mean.is_nan() && stats.median.is_nan());
+    }
+}
@@ -0,0 +1,293 @@
+// ---------------------------------------------------------------------------
+// Warunki zatrzymania
+//
+// Trybu Auto, serii "Uruchom" ani przebiegu `run` z linii poleceń nie trzeba
+// przerywać ręcznie – ewolucja kończy się, gdy spełniony zostanie którykolwiek
+// z włączonych warunków:
+//   - osiągnięto zadany numer pokolenia,
+//   - najlepsza wartość funkcji celu osiągnęła cel (z uwzględnieniem
+//     kierunku: przy minimalizacji "osiągnęła" znaczy "spadła do"),
+//   - najlepszy wynik nie poprawił się przez N pokoleń (stagnacja),
+//   - populacja stała się zbyt jednorodna (patrz `Population::spread`),
+//   - upłynął limit czasu.
+//
+// Stagnacja i czas wymagają pamięci między pokoleniami – przechowuje ją
+// `StopMonitor`, tworzony na początku każdej serii i każdego przebiegu Auto.
+// ---------------------------------------------------------------------------
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::VarSpec;
+use crate::fitness::{Direction, FitnessMode};
+use crate::population::Population;
+
+/// Warunek, który można włączyć lub wyłączyć bez utraty ustawionej wartości.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Criterion<T> {
+    pub enabled: bool,
+    pub value:   T,
+}
+
+impl<T> Criterion<T> {
+    fn off(value: T) -> Self {
+        Self { enabled: false, value }
+    }
+
+    /// Wartość warunku, o ile jest włączony.
+    pub fn get(&self) -> Option<&T> {
+        self.enabled.then_some(&self.value)
+    }
+}
+
+/// Zestaw warunków zatrzymania; domyślnie wszystkie wyłączone.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StopCriteria {
+    /// Numer pokolenia, na którym kończymy.
+    pub max_generations: Criterion<usize>,
+    /// Docelowa wartość funkcji celu najlepszego osobnika.
+    pub target_value:    Criterion<f64>,
+    /// Liczba pokoleń bez poprawy najlepszego wyniku.
+    pub stagnation:      Criterion<usize>,
+    /// Minimalna różnorodność populacji (ułamek szerokości dziedziny).
+    pub min_diversity:   Criterion<f64>,
+    /// Limit czasu w sekundach.
+    pub time_limit:      Criterion<f64>,
+}
+
+impl Default for StopCriteria {
+    fn default() -> Self {
+        Self {
+            max_generations: Criterion::off(500),
+            target_value:    Criterion::off(0.0),
+            stagnation:      Criterion::off(50),
+            min_diversity:   Criterion::off(0.001),
+            time_limit:      Criterion::off(60.0),
+        }
+    }
+}
+
+impl StopCriteria {
+    /// Sprawdza wartości włączonych warunków; zwraca opis pierwszego błędu.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.stagnation.get().is_some_and(|&n| n < 1) {
+            return Err("liczba pokoleń stagnacji musi wynosić co najmniej 1".into());
+        }
+        if self.target_value.get().is_some_and(|v| !v.is_finite()) {
+            return Err("docelowa wartość funkcji celu musi być skończona".into());
+        }
+        if self.min_diversity.get().is_some_and(|&d| !(d >= 0.0 && d.is_finite())) {
+            return Err("próg różnorodności nie może być ujemny".into());
+        }
+        if self.time_limit.get().is_some_and(|&t| !(t > 0.0 && t.is_finite())) {
+            return Err("limit czasu musi być dodatni".into());
+        }
+        Ok(())
+    }
+}
+
+/// Powód zakończenia ewolucji.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StopReason {
+    MaxGenerations(usize),
+    TargetReached(f64),
+    Stagnation(usize),
+    LowDiversity(f64),
+    TimeLimit(Duration),
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::MaxGenerations(n) => write!(f, "osiągnięto pokolenie {n}"),
+            StopReason::TargetReached(v)  => write!(f, "osiągnięto wartość docelową (f = {v:.4})"),
+            StopReason::Stagnation(n)     => write!(f, "brak poprawy przez {n} pokoleń"),
+            StopReason::LowDiversity(d)   => write!(f, "różnorodność populacji spadła do {d:.2e}"),
+            StopReason::TimeLimit(t)      => write!(f, "upłynął limit czasu ({:.2} s)", t.as_secs_f64()),
+        }
+    }
+}
+
+/// Pamięć warunków zatrzymania między pokoleniami jednego przebiegu.
+#[derive(Clone, Debug)]
+pub struct StopMonitor {
+    started:          Instant,
+    /// Najlepsza dotąd zorientowana wartość (większa = lepsza).
+    best_so_far:      f64,
+    /// Pokolenie, w którym ostatnio poprawił się najlepszy wynik.
+    last_improvement: usize,
+}
+
+impl StopMonitor {
+    /// Zaczyna obserwację od bieżącej populacji; czas liczony od teraz.
+    pub fn new(pop: &Population, mode: &FitnessMode) -> Self {
+        Self {
+            started:          Instant::now(),
+            best_so_far:      best_oriented(pop, mode),
+            last_improvement: pop.generation,
+        }
+    }
+
+    /// Rejestruje nowe pokolenie i sprawdza włączone warunki.
+    pub fn observe(
+        &mut self,
+        pop: &Population,
+        criteria: &StopCriteria,
+        mode: &FitnessMode,
+        vars: &[VarSpec],
+    ) -> Option<StopReason> {
+        let best = best_oriented(pop, mode);
+        if best > self.best_so_far {
+            self.best_so_far = best;
+            self.last_improvement = pop.generation;
+        }
+
+        if let Some(&max) = criteria.max_generations.get()
+            && pop.generation >= max
+        {
+            return Some(StopReason::MaxGenerations(pop.generation));
+        }
+        if let (Some(&target), Some(c)) = (criteria.target_value.get(), pop.best()) {
+            let reached = match mode.direction {
+                Direction::Maximize => c.value >= target,
+                Direction::Minimize => c.value <= target,
+            };
+            if reached {
+                return Some(StopReason::TargetReached(c.value));
+            }
+        }
+        if let Some(&n) = criteria.stagnation.get() {
+            let stalled = pop.generation.saturating_sub(self.last_improvement);
+            if stalled >= n {
+                return Some(StopReason::Stagnation(stalled));
+            }
+        }
+        if let Some(&threshold) = criteria.min_diversity.get() {
+            let spread = pop.spread(vars);
+            if spread < threshold {
+                return Some(StopReason::LowDiversity(spread));
+            }
+        }
+        if let Some(&limit) = criteria.time_limit.get() {
+            let elapsed = self.started.elapsed();
+            if elapsed.as_secs_f64() >= limit {
+                return Some(StopReason::TimeLimit(elapsed));
+            }
+        }
+        None
+    }
+}
+
+fn best_oriented(pop: &Population, mode: &FitnessMode) -> f64 {
+    pop.best().map_or(f64::NEG_INFINITY, |c| mode.oriented(c.value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromosome::Chromosome;
+
+    fn vars() -> Vec<VarSpec> {
+        vec![VarSpec::default()]
+    }
+
+    /// Populacja osobników w punktach `xs` z wartością f(x) = x.
+    fn pop(xs: &[f64], generation: usize, mode: &FitnessMode) -> Population {
+        let chromosomes = xs
+            .iter()
+            .map(|&x| Chromosome { value: x, ..Chromosome::from_xs(vec![x], &vars()) })
+            .collect();
+        Population::from_chromosomes(chromosomes, generation, mode)
+    }
+
+    fn only(criteria: impl FnOnce(&mut StopCriteria)) -> StopCriteria {
+        let mut c = StopCriteria::default();
+        criteria(&mut c);
+        c
+    }
+
+    #[test]
+    fn disabled_criteria_never_stop() {
+        let mode = FitnessMode::default();
+        let mut monitor = StopMonitor::new(&pop(&[1.0, 1.0], 0, &mode), &mode);
+        let p = pop(&[1.0, 1.0], 10_000, &mode);
+        assert_eq!(monitor.observe(&p, &StopCriteria::default(), &mode, &vars()), None);
+    }
+
+    #[test]
+    fn max_generations_stops_at_the_given_generation() {
+        let mode = FitnessMode::default();
+        let criteria = only(|c| c.max_generations = Criterion { enabled: true, value: 3 });
+        let mut monitor = StopMonitor::new(&pop(&[0.0, 5.0], 0, &mode), &mode);
+        assert_eq!(monitor.observe(&pop(&[0.0, 5.0], 2, &mode), &criteria, &mode, &vars()), None);
+        let reason = monitor.observe(&pop(&[0.0, 5.0], 3, &mode), &criteria, &mode, &vars());
+        assert_eq!(reason, Some(StopReason::MaxGenerations(3)));
+    }
+
+    #[test]
+    fn target_respects_direction() {
+        let criteria = only(|c| c.target_value = Criterion { enabled: true, value: 1.0 });
+        let max = FitnessMode::default();
+        let min = FitnessMode { direction: Direction::Minimize, ..max };
+        for (mode, xs, reached) in [
+            (max, [0.5, 2.0], Some(2.0)),
+            (max, [0.5, 0.9], None),
+            (min, [0.5, 2.0], Some(0.5)),
+            (min, [1.5, 2.0], None),
+        ] {
+            let p = pop(&xs, 1, &mode);
+            let reason = StopMonitor::new(&p, &mode).observe(&p, &criteria, &mode, &vars());
+            assert_eq!(reason, reached.map(StopReason::TargetReached), "{:?} {xs:?}", mode.direction);
+        }
+    }
+
+    #[test]
+    fn stagnation_counts_generations_without_improvement() {
+        let mode = FitnessMode::default();
+        let criteria = only(|c| c.stagnation = Criterion { enabled: true, value: 3 });
+        let mut monitor = StopMonitor::new(&pop(&[1.0, 0.0], 0, &mode), &mode);
+        assert_eq!(monitor.observe(&pop(&[1.0, 0.0], 2, &mode), &criteria, &mode, &vars()), None);
+        // Poprawa w pokoleniu 3 zeruje licznik.
+        assert_eq!(monitor.observe(&pop(&[2.0, 0.0], 3, &mode), &criteria, &mode, &vars()), None);
+        assert_eq!(monitor.observe(&pop(&[2.0, 0.0], 5, &mode), &criteria, &mode, &vars()), None);
+        let reason = monitor.observe(&pop(&[2.0, 0.0], 6, &mode), &criteria, &mode, &vars());
+        assert_eq!(reason, Some(StopReason::Stagnation(3)));
+    }
+
+    #[test]
+    fn low_diversity_stops_a_collapsed_population() {
+        let mode = FitnessMode::default();
+        let criteria = only(|c| c.min_diversity = Criterion { enabled: true, value: 0.01 });
+        let spread = pop(&[-5.0, 5.0], 1, &mode);
+        let collapsed = pop(&[2.0, 2.0, 2.0], 1, &mode);
+        let mut monitor = StopMonitor::new(&spread, &mode);
+        assert_eq!(monitor.observe(&spread, &criteria, &mode, &vars()), None);
+        let reason = monitor.observe(&collapsed, &criteria, &mode, &vars());
+        assert_eq!(reason, Some(StopReason::LowDiversity(0.0)));
+    }
+
+    #[test]
+    fn time_limit_is_measured_from_monitor_start() {
+        let mode = FitnessMode::default();
+        let p = pop(&[0.0, 1.0], 1, &mode);
+        let criteria = only(|c| c.time_limit = Criterion { enabled: true, value: 1e-3 });
+        let mut monitor = StopMonitor::new(&p, &mode);
+        std::thread::sleep(Duration::from_millis(5));
+        let reason = monitor.observe(&p, &criteria, &mode, &vars());
+        assert!(matches!(reason, Some(StopReason::TimeLimit(t)) if t >= Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn only_enabled_values_are_validated() {
+        assert_eq!(StopCriteria::default().validate(), Ok(()));
+        let disabled = only(|c| c.time_limit.value = -1.0);
+        assert_eq!(disabled.validate(), Ok(()));
+        assert!(only(|c| c.time_limit = Criterion { enabled: true, value: 0.0 }).validate().is_err());
+        assert!(only(|c| c.stagnation = Criterion { enabled: true, value: 0 }).validate().is_err());
+        assert!(only(|c| c.target_value = Criterion { enabled: true, value: f64::NAN }).validate().is_err());
+        assert!(only(|c| c.min_diversity = Criterion { enabled: true, value: -0.1 }).validate().is_err());
+    }
+}